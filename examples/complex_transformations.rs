@@ -159,7 +159,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     assert_eq!(profile.contact.formatted_address, "123 Main St, Springfield, IL 62701");
     assert_eq!(profile.contact.phone, Some("+1-555-123-4567".to_string()));
     assert_eq!(profile.preferences.theme, "dark");
-    assert_eq!(profile.preferences.notifications_enabled, true);
+    assert!(profile.preferences.notifications_enabled);
     assert_eq!(profile.preferences.language, "en-US");
     assert_eq!(profile.tags, vec!["premium", "verified", "active"]);
     assert_eq!(profile.status, "ACTIVE");
@@ -0,0 +1,434 @@
+use crate::error::EnrichmentError;
+use crate::jsonpath::{self, JsonPathExtractor};
+use crate::lookup::LookupTable;
+use crate::provider::{Clock, RandomUuidGenerator, SystemClock, TokenVault, UuidGenerator};
+use crate::traits::Enrichable;
+use crate::transform::{self, coercions, format_date, DateValue};
+use crate::types::{JsonPath, MappingRule, NowSource, Spec, Target, UuidSource, WriteMode};
+use serde::Serialize;
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Applies mapping specs to enrichable targets using caller-supplied runtime context
+pub struct Engine {
+    context: HashMap<String, Value>,
+    clock: Box<dyn Clock>,
+    uuids: Box<dyn UuidGenerator>,
+    sequences: Mutex<HashMap<String, u64>>,
+    coerce: bool,
+    tables: HashMap<String, LookupTable>,
+    hash_keys: HashMap<String, Vec<u8>>,
+    vault: Option<Box<dyn TokenVault>>,
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self {
+            context: HashMap::new(),
+            clock: Box::new(SystemClock),
+            uuids: Box::new(RandomUuidGenerator),
+            sequences: Mutex::new(HashMap::new()),
+            coerce: false,
+            tables: HashMap::new(),
+            hash_keys: HashMap::new(),
+            vault: None,
+        }
+    }
+}
+
+impl Engine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use the given clock for `now` sources
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Use the given generator for `uuid` sources
+    pub fn with_uuid_generator(mut self, uuids: impl UuidGenerator + 'static) -> Self {
+        self.uuids = Box::new(uuids);
+        self
+    }
+
+    /// Retry values that do not fit a field's type as a number, boolean or string before failing,
+    /// so `"42"` can fill an integer field and `42` a string field
+    pub fn with_coercion(mut self, coerce: bool) -> Self {
+        self.coerce = coerce;
+        self
+    }
+
+    /// Register a table that `lookup` transforms can reference by name
+    pub fn with_lookup_table(mut self, name: impl Into<String>, table: LookupTable) -> Self {
+        self.tables.insert(name.into(), table);
+        self
+    }
+
+    /// Register a secret key that `hash` transforms reference by name, keeping it out of spec files
+    pub fn with_hash_key(mut self, name: impl Into<String>, key: impl Into<Vec<u8>>) -> Self {
+        self.hash_keys.insert(name.into(), key.into());
+        self
+    }
+
+    /// Use the given vault for `tokenize` transforms
+    pub fn with_token_vault(mut self, vault: impl TokenVault + 'static) -> Self {
+        self.vault = Some(Box::new(vault));
+        self
+    }
+
+    /// Add a runtime context value, overriding any spec variable of the same name
+    pub fn with_var(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.context.insert(name.into(), value);
+        self
+    }
+
+    /// Set a runtime context value, overriding any spec variable of the same name
+    pub fn set_var(&mut self, name: impl Into<String>, value: Value) {
+        self.context.insert(name.into(), value);
+    }
+
+    /// Return the next value of a named sequence, starting at 1
+    fn next_sequence(&self, name: &str) -> Value {
+        let mut sequences = self.sequences.lock().unwrap_or_else(|e| e.into_inner());
+        let counter = sequences.entry(name.to_string()).or_insert(0);
+        *counter += 1;
+        Value::from(*counter)
+    }
+
+    /// Enrich the target with data according to the provided spec
+    pub fn enrich<T>(&self, target: &mut T, data: &HashMap<String, Value>, spec: &Spec) -> Result<(), EnrichmentError>
+    where
+        T: Enrichable + ?Sized,
+    {
+        let root = Value::Object(data.clone().into_iter().collect());
+        self.apply_rules(target, &root, &spec.rules, &spec.vars)
+    }
+
+    /// Enrich the target from any JSON document, including array and scalar roots
+    pub fn enrich_value<T>(&self, target: &mut T, data: &Value, spec: &Spec) -> Result<(), EnrichmentError>
+    where
+        T: Enrichable + ?Sized,
+    {
+        self.apply_rules(target, data, &spec.rules, &spec.vars)
+    }
+
+    /// Enrich the target from any serializable source, such as another domain struct
+    pub fn enrich_from<T, S>(&self, target: &mut T, source: &S, spec: &Spec) -> Result<(), EnrichmentError>
+    where
+        T: Enrichable + ?Sized,
+        S: Serialize + ?Sized,
+    {
+        self.enrich_value(target, &serde_json::to_value(source)?, spec)
+    }
+
+    pub(crate) fn apply_rules<T>(
+        &self,
+        target: &mut T,
+        root: &Value,
+        rules: &[MappingRule],
+        vars: &HashMap<String, Value>,
+    ) -> Result<(), EnrichmentError>
+    where
+        T: Enrichable + ?Sized,
+    {
+        let scope = Scope { engine: self, vars, root };
+
+        for rule in rules {
+            scope.apply_rule(rule, &mut |path, value| {
+                let field = field_name(path)?;
+                let current = match rule.mode {
+                    WriteMode::Overwrite => None,
+                    _ => Some(target.get_field(field)?),
+                };
+                // A scalar appended to an empty field is set as-is, or as a one-element array when the field is a list
+                let wrap = (rule.mode == WriteMode::Append && current.as_ref().is_some_and(Value::is_null) && !value.is_array())
+                    .then(|| Value::Array(vec![value.clone()]));
+                match combine(rule.mode, path, current, value)? {
+                    Some(value) => match (scope.set_field(target, field, value), wrap) {
+                        (Err(EnrichmentError::DataTypeError(_)), Some(wrapped)) => scope.set_field(target, field, wrapped),
+                        (result, _) => result,
+                    },
+                    None => Ok(()),
+                }
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Map a JSON document to a new one, writing each rule's value at its target path.
+    /// Missing intermediate objects and arrays are created; a spec that writes nothing produces `{}`.
+    pub fn transform_value(&self, data: &Value, spec: &Spec) -> Result<Value, EnrichmentError> {
+        let scope = Scope { engine: self, vars: &spec.vars, root: data };
+        let mut output = Value::Null;
+
+        for rule in &spec.rules {
+            scope.apply_rule(rule, &mut |path, value| {
+                let current = match rule.mode {
+                    WriteMode::Overwrite => None,
+                    _ => Some(jsonpath::read(&output, path)?.cloned().unwrap_or(Value::Null)),
+                };
+                match combine(rule.mode, path, current, value)? {
+                    Some(value) => jsonpath::write(&mut output, path, value),
+                    None => Ok(()),
+                }
+            })?;
+        }
+        Ok(if output.is_null() { Value::Object(Default::default()) } else { output })
+    }
+}
+
+/// Variables and input document visible while applying a single spec
+pub(crate) struct Scope<'a> {
+    engine: &'a Engine,
+    vars: &'a HashMap<String, Value>,
+    root: &'a Value,
+}
+
+impl Scope<'_> {
+    /// Look up a variable, preferring runtime context over spec-level vars
+    pub(crate) fn var(&self, name: &str) -> Option<&Value> {
+        self.engine.context.get(name).or_else(|| self.vars.get(name))
+    }
+
+    /// Find a lookup table registered on the engine
+    pub(crate) fn table(&self, name: &str) -> Result<&LookupTable, EnrichmentError> {
+        self.engine.tables.get(name)
+            .ok_or_else(|| EnrichmentError::SpecError(format!("Unknown lookup table: {}", name)))
+    }
+
+    /// Find a hash key registered on the engine
+    pub(crate) fn hash_key(&self, name: &str) -> Result<&[u8], EnrichmentError> {
+        self.engine.hash_keys.get(name)
+            .map(Vec::as_slice)
+            .ok_or_else(|| EnrichmentError::SpecError(format!("Unknown hash key: {}", name)))
+    }
+
+    pub(crate) fn token_vault(&self) -> Result<&dyn TokenVault, EnrichmentError> {
+        self.engine.vault.as_deref()
+            .ok_or_else(|| EnrichmentError::SpecError("No token vault configured on the engine".into()))
+    }
+
+    /// Extract the value at a source path, resolving `${var}` references in the path
+    pub(crate) fn select(&self, path: &str) -> Result<Value, EnrichmentError> {
+        JsonPathExtractor::select(self.root, &self.interpolate(path)?)
+    }
+
+    /// Replace every `${name}` in the input with the string form of the variable
+    pub(crate) fn interpolate<'s>(&self, input: &'s str) -> Result<Cow<'s, str>, EnrichmentError> {
+        if !input.contains("${") {
+            return Ok(Cow::Borrowed(input));
+        }
+
+        let mut result = String::with_capacity(input.len());
+        let mut rest = input;
+        while let Some(start) = rest.find("${") {
+            result.push_str(&rest[..start]);
+            let end = rest[start..].find('}')
+                .ok_or_else(|| EnrichmentError::SpecError(format!("Unterminated variable reference in '{}'", input)))?;
+            let name = &rest[start + 2..start + end];
+            match self.var(name) {
+                Some(Value::String(s)) => result.push_str(s),
+                Some(value) => result.push_str(&value.to_string()),
+                None => return Err(EnrichmentError::SpecError(format!("Unknown variable: {}", name))),
+            }
+            rest = &rest[start + end + 1..];
+        }
+        result.push_str(rest);
+        Ok(Cow::Owned(result))
+    }
+
+    /// Resolve a constant, keeping the variable's own type when the whole string is a single reference
+    fn resolve_const(&self, value: &Value) -> Result<Value, EnrichmentError> {
+        match value {
+            Value::String(s) => {
+                if let Some(name) = s.strip_prefix("${").and_then(|s| s.strip_suffix('}')) {
+                    if !name.contains(['$', '{', '}']) {
+                        return self.var(name)
+                            .cloned()
+                            .ok_or_else(|| EnrichmentError::SpecError(format!("Unknown variable: {}", name)));
+                    }
+                }
+                Ok(Value::String(self.interpolate(s)?.into_owned()))
+            }
+            other => Ok(other.clone()),
+        }
+    }
+
+    /// Produce the value of a rule source, reading the input document or computing it
+    pub(crate) fn resolve_source(&self, source: &JsonPath) -> Result<Value, EnrichmentError> {
+        match source {
+            JsonPath::Single(path) => self.select(path),
+            JsonPath::Multiple(paths) => {
+                let values = paths.iter()
+                    .map(|path| self.select(path))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Value::Array(values))
+            }
+            JsonPath::Const { value } => self.resolve_const(value),
+            JsonPath::Now { now } => self.now(now),
+            JsonPath::Uuid { uuid } => Ok(self.uuid(uuid)),
+            JsonPath::Sequence { sequence } => Ok(self.engine.next_sequence(&self.interpolate(sequence)?)),
+            JsonPath::Context { context } => self.var(context)
+                .cloned()
+                .ok_or_else(|| EnrichmentError::SpecError(format!("Unknown context value: {}", context))),
+            JsonPath::Object { object } => {
+                let fields = object.iter()
+                    .map(|(key, source)| Ok((key.clone(), self.resolve_source(source)?)))
+                    .collect::<Result<_, EnrichmentError>>()?;
+                Ok(Value::Object(fields))
+            }
+            JsonPath::Coalesce { coalesce, default } => {
+                for path in coalesce {
+                    match JsonPathExtractor::select_optional(self.root, &self.interpolate(path)?)? {
+                        Some(Value::Null) | None => continue,
+                        Some(value) => return Ok(value),
+                    }
+                }
+                match default {
+                    Some(default) => self.resolve_const(default),
+                    None => Ok(Value::Null),
+                }
+            }
+        }
+    }
+
+    fn now(&self, source: &NowSource) -> Result<Value, EnrichmentError> {
+        let now = DateValue::DateTime(self.engine.clock.now().fixed_offset());
+        match source.format.as_deref() {
+            Some(format) => format_date(now, &self.interpolate(format)?),
+            None => Ok(now.to_value()),
+        }
+    }
+
+    fn uuid(&self, source: &UuidSource) -> Value {
+        let uuid = self.engine.uuids.generate();
+        if source.simple {
+            Value::String(uuid.simple().to_string())
+        } else {
+            Value::String(uuid.hyphenated().to_string())
+        }
+    }
+
+    /// Resolve and transform a rule's source, passing each target path and its value to `write`
+    fn apply_rule(
+        &self,
+        rule: &MappingRule,
+        write: &mut dyn FnMut(&str, Value) -> Result<(), EnrichmentError>,
+    ) -> Result<(), EnrichmentError> {
+        // Extract source value(s)
+        let source_value = self.resolve_source(&rule.source)?;
+
+        // Apply transformation if specified
+        let transformed_value = match &rule.transform {
+            Some(transform) => transform::apply(transform, &source_value, self)?,
+            None => source_value,
+        };
+
+        // Update target field(s)
+        match &rule.target {
+            Target::Single(path) => {
+                write(&self.interpolate(path)?, transformed_value)?;
+            }
+            Target::Multiple(paths) => {
+                if let Value::Array(values) = transformed_value {
+                    for (path, value) in paths.iter().zip(values) {
+                        write(&self.interpolate(path)?, value)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Set a field, falling back to coerced forms of the value when the engine allows it
+    fn set_field<T>(&self, target: &mut T, field: &str, value: Value) -> Result<(), EnrichmentError>
+    where
+        T: Enrichable + ?Sized,
+    {
+        if !self.engine.coerce {
+            return target.set_field(field, value);
+        }
+
+        let candidates = coercions(&value);
+        match target.set_field(field, value) {
+            Err(EnrichmentError::DataTypeError(message)) => {
+                for candidate in candidates {
+                    if target.set_field(field, candidate).is_ok() {
+                        return Ok(());
+                    }
+                }
+                Err(EnrichmentError::DataTypeError(message))
+            }
+            result => result,
+        }
+    }
+}
+
+/// Map a JSON document to a new one using a default engine, see [`Engine::transform_value`]
+pub fn transform_value(data: &Value, spec: &Spec) -> Result<Value, EnrichmentError> {
+    Engine::new().transform_value(data, spec)
+}
+
+/// Combine a rule's value with the target's current value; `None` leaves the target unchanged
+fn combine(mode: WriteMode, path: &str, current: Option<Value>, value: Value) -> Result<Option<Value>, EnrichmentError> {
+    let current = match (mode, current) {
+        (WriteMode::Overwrite, _) | (_, None) => return Ok(Some(value)),
+        (_, Some(current)) => current,
+    };
+    let conflict = |current: &Value, value: &Value| {
+        EnrichmentError::DataTypeError(format!(
+            "Cannot {} {} into {} at '{}'",
+            if mode == WriteMode::Append { "append" } else { "merge" },
+            jsonpath::type_name(value),
+            jsonpath::type_name(current),
+            path
+        ))
+    };
+
+    match mode {
+        WriteMode::Overwrite => Ok(Some(value)),
+        WriteMode::IfEmpty => {
+            let empty = match &current {
+                Value::Null => true,
+                Value::Bool(b) => !b,
+                Value::Number(n) => n.as_f64() == Some(0.0),
+                Value::String(s) => s.is_empty(),
+                Value::Array(values) => values.is_empty(),
+                Value::Object(fields) => fields.is_empty(),
+            };
+            Ok(empty.then_some(value))
+        }
+        WriteMode::Append => match (current, value) {
+            (Value::Null, Value::Array(values)) => Ok(Some(Value::Array(values))),
+            (Value::Null, value) => Ok(Some(value)),
+            (Value::Array(mut values), Value::Array(more)) => {
+                values.extend(more);
+                Ok(Some(Value::Array(values)))
+            }
+            (Value::Array(mut values), value) => {
+                values.push(value);
+                Ok(Some(Value::Array(values)))
+            }
+            (Value::String(text), Value::String(more)) => Ok(Some(Value::String(text + &more))),
+            (current, value) => Err(conflict(&current, &value)),
+        },
+        WriteMode::Merge => match (current, value) {
+            (Value::Null, value) => Ok(Some(value)),
+            (Value::Object(mut fields), Value::Object(more)) => {
+                transform::merge_objects(&mut fields, &more, true);
+                Ok(Some(Value::Object(fields)))
+            }
+            (current, value) => Err(conflict(&current, &value)),
+        },
+    }
+}
+
+fn field_name(path: &str) -> Result<&str, EnrichmentError> {
+    path.split('.').next_back()
+        .map(|name| name.trim_start_matches('$').trim_start_matches('.'))
+        .ok_or_else(|| EnrichmentError::SpecError("Invalid target path".into()))
+}
@@ -0,0 +1,73 @@
+use crate::error::EnrichmentError;
+use csv::{StringRecord, StringRecordsIntoIter};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::Read;
+
+/// Reads CSV files with a header row, producing one enrichment input per row keyed by column name.
+/// Fields stay strings, with empty fields as `""`.
+#[derive(Debug, Clone)]
+pub struct CsvParser {
+    delimiter: u8,
+    trim: bool,
+}
+
+/// Streaming iterator over the rows of a CSV file
+pub struct CsvRecords<R> {
+    headers: StringRecord,
+    records: StringRecordsIntoIter<R>,
+}
+
+impl CsvParser {
+    pub fn new() -> Self {
+        Self { delimiter: b',', trim: true }
+    }
+
+    /// Field delimiter, such as `b';'` for files exported with a European locale
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Whether to trim whitespace around headers and fields, enabled by default
+    pub fn with_trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Read the header row and return an iterator over the remaining rows
+    pub fn records<R: Read>(&self, reader: R) -> Result<CsvRecords<R>, EnrichmentError> {
+        let mut csv = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .trim(if self.trim { csv::Trim::All } else { csv::Trim::None })
+            .from_reader(reader);
+        let headers = csv.headers()
+            .map_err(|e| EnrichmentError::DataTypeError(format!("Invalid CSV header: {}", e)))?
+            .clone();
+        if let Some(duplicate) = headers.iter().enumerate().find(|(i, name)| headers.iter().take(*i).any(|other| other == *name)) {
+            return Err(EnrichmentError::DataTypeError(format!("Duplicate CSV column '{}'", duplicate.1)));
+        }
+        Ok(CsvRecords { headers, records: csv.into_records() })
+    }
+}
+
+impl Default for CsvParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: Read> Iterator for CsvRecords<R> {
+    type Item = Result<HashMap<String, Value>, EnrichmentError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = match self.records.next()? {
+            Ok(record) => record,
+            Err(e) => return Some(Err(EnrichmentError::DataTypeError(format!("Invalid CSV row: {}", e)))),
+        };
+        Some(Ok(self.headers.iter()
+            .zip(record.iter())
+            .map(|(name, field)| (name.to_string(), Value::String(field.to_string())))
+            .collect()))
+    }
+}
@@ -0,0 +1,99 @@
+use crate::error::EnrichmentError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{BufRead, Lines};
+
+/// Column positions of a fixed-width record format, usually loaded from JSON:
+/// `{"columns": [{"name": "account", "start": 1, "length": 10}, ...]}`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FixedWidthLayout {
+    pub columns: Vec<FixedWidthColumn>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FixedWidthColumn {
+    pub name: String,
+    /// 1-based position of the first character, as written in bank format specifications
+    pub start: usize,
+    /// Number of characters
+    pub length: usize,
+    /// Whether to strip padding spaces from the value
+    #[serde(default = "default_trim")]
+    pub trim: bool,
+}
+
+fn default_trim() -> bool {
+    true
+}
+
+/// Streaming iterator over the non-blank lines of a fixed-width file
+pub struct FixedWidthRecords<R> {
+    layout: FixedWidthLayout,
+    lines: Lines<R>,
+    line: usize,
+}
+
+impl FixedWidthLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a trimmed column of `length` characters starting at the 1-based position `start`
+    pub fn with_column(mut self, name: impl Into<String>, start: usize, length: usize) -> Self {
+        self.columns.push(FixedWidthColumn { name: name.into(), start, length, trim: true });
+        self
+    }
+
+    /// Check the layout and return an iterator over the records of the input
+    pub fn records<R: BufRead>(&self, reader: R) -> Result<FixedWidthRecords<R>, EnrichmentError> {
+        for (i, column) in self.columns.iter().enumerate() {
+            if column.start == 0 || column.length == 0 {
+                return Err(EnrichmentError::SpecError(format!(
+                    "Fixed-width column '{}' needs a start of at least 1 and a non-zero length", column.name
+                )));
+            }
+            if self.columns[..i].iter().any(|other| other.name == column.name) {
+                return Err(EnrichmentError::SpecError(format!("Duplicate fixed-width column '{}'", column.name)));
+            }
+        }
+        Ok(FixedWidthRecords { layout: self.clone(), lines: reader.lines(), line: 0 })
+    }
+
+    /// Split one line into its columns; columns past the end of a short line are empty
+    pub fn parse_line(&self, line: &str) -> HashMap<String, Value> {
+        let chars: Vec<char> = line.chars().collect();
+        self.columns.iter()
+            .map(|column| {
+                let start = column.start.saturating_sub(1).min(chars.len());
+                let end = start.saturating_add(column.length).min(chars.len());
+                let text: String = chars[start..end].iter().collect();
+                let text = if column.trim { text.trim().to_string() } else { text };
+                (column.name.clone(), Value::String(text))
+            })
+            .collect()
+    }
+}
+
+impl<R: BufRead> Iterator for FixedWidthRecords<R> {
+    type Item = Result<HashMap<String, Value>, EnrichmentError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line += 1;
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => {
+                    return Some(Err(EnrichmentError::DataTypeError(format!(
+                        "Cannot read fixed-width line {}: {}", self.line, e
+                    ))))
+                }
+            };
+            if !line.trim().is_empty() {
+                return Some(Ok(self.layout.parse_line(&line)));
+            }
+        }
+    }
+}
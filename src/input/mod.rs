@@ -0,0 +1,11 @@
+mod delimited;
+mod fixed_width;
+mod mt;
+#[cfg(feature = "xml")]
+mod xml;
+
+pub use delimited::{CsvParser, CsvRecords};
+pub use fixed_width::{FixedWidthColumn, FixedWidthLayout, FixedWidthRecords};
+pub use mt::MtParser;
+#[cfg(feature = "xml")]
+pub use xml::XmlParser;
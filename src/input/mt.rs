@@ -0,0 +1,317 @@
+use crate::error::EnrichmentError;
+use chrono::{Datelike, NaiveDate};
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+
+/// Converts SWIFT MT messages such as MT103 and MT940 into enrichment input.
+///
+/// The message becomes an object with one key per block present:
+/// - `block1`: basic header split into `applicationId`, `serviceId`, `logicalTerminal`, `session` and `sequence`
+/// - `block2`: application header with `direction` (`input` or `output`), `messageType` and the
+///   remaining header fields of that direction
+/// - `block3` and `block5`: user header and trailer as objects of their `{tag:value}` sub-blocks
+/// - `block4`: text block keyed by tag, such as `20` or `32A`
+///
+/// Text block values are strings, with continuation lines joined by `\n`. Common composite tags are
+/// split into objects: currency/amount tags such as `32A`, `32B`, `33B`, `71F` and `71G`, balances
+/// (`60F`, `60M`, `62F`, `62M`, `64`, `65`), statement lines (`61`), and parties with an optional
+/// `/account` line (`50A`, `50F`, `50K`, `52A`, `53A`, `54A`, `56A`, `57A`, `59`, `59A`, `59F`).
+/// Dates become `YYYY-MM-DD` and amounts use `.` as decimal separator, kept as strings.
+///
+/// A tag occurring more than once becomes an array in message order; tags registered with
+/// [`MtParser::with_array`] become arrays even when they occur once. Input without `{1:` blocks,
+/// such as a bare MT940 statement, is read as a text block alone.
+#[derive(Debug, Clone, Default)]
+pub struct MtParser {
+    arrays: HashSet<String>,
+}
+
+impl MtParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Always convert this text block tag to an array, such as `61` in MT940 statements
+    pub fn with_array(mut self, tag: impl Into<String>) -> Self {
+        self.arrays.insert(tag.into());
+        self
+    }
+
+    pub fn parse_reader(&self, mut reader: impl Read) -> Result<HashMap<String, Value>, EnrichmentError> {
+        let mut message = String::new();
+        reader.read_to_string(&mut message)
+            .map_err(|e| error(format!("cannot read message: {}", e)))?;
+        self.parse_str(&message)
+    }
+
+    pub fn parse_str(&self, message: &str) -> Result<HashMap<String, Value>, EnrichmentError> {
+        let message = message.trim();
+        let mut data = HashMap::new();
+        if !message.starts_with('{') {
+            data.insert("block4".to_string(), self.text_block(message)?);
+            return Ok(data);
+        }
+
+        for (id, content) in blocks(message)? {
+            let value = match id.as_str() {
+                "1" => basic_header(&content)?,
+                "2" => application_header(&content)?,
+                "3" | "5" => Value::Object(blocks(&content)?.into_iter().map(|(tag, value)| (tag, Value::String(value))).collect()),
+                "4" => self.text_block(&content)?,
+                _ => return Err(error(format!("unknown block {{{}:", id))),
+            };
+            if data.insert(format!("block{}", id), value).is_some() {
+                return Err(error(format!("block {{{}: occurs twice", id)));
+            }
+        }
+        Ok(data)
+    }
+
+    fn text_block(&self, content: &str) -> Result<Value, EnrichmentError> {
+        let mut fields: Vec<(String, String)> = Vec::new();
+        for line in content.lines() {
+            let line = line.trim_end_matches('\r');
+            if line == "-" {
+                break;
+            }
+            match field_start(line) {
+                Some((tag, value)) => fields.push((tag.to_string(), value.to_string())),
+                None => match fields.last_mut() {
+                    Some((_, value)) => {
+                        value.push('\n');
+                        value.push_str(line);
+                    }
+                    None if line.trim().is_empty() => {}
+                    None => return Err(error(format!("text block starts without a tag: '{}'", line))),
+                },
+            }
+        }
+
+        let mut object = Map::new();
+        for (tag, value) in fields {
+            let value = subfields(&tag, &value)
+                .map_err(|reason| error(format!("field :{}: '{}' {}", tag, value, reason)))?;
+            match object.get_mut(&tag) {
+                Some(Value::Array(values)) => values.push(value),
+                Some(existing) => *existing = Value::Array(vec![existing.take(), value]),
+                None if self.arrays.contains(&tag) => {
+                    object.insert(tag, Value::Array(vec![value]));
+                }
+                None => {
+                    object.insert(tag, value);
+                }
+            }
+        }
+        Ok(Value::Object(object))
+    }
+}
+
+fn error(message: String) -> EnrichmentError {
+    EnrichmentError::DataTypeError(format!("Invalid MT message: {}", message))
+}
+
+/// Split `{id:content}{id:content}...` at the top level, keeping nested braces in the content
+fn blocks(text: &str) -> Result<Vec<(String, String)>, EnrichmentError> {
+    let mut blocks = Vec::new();
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        let inner = rest.strip_prefix('{')
+            .ok_or_else(|| error(format!("expected '{{' before '{}'", rest.chars().take(20).collect::<String>())))?;
+        let mut depth = 1;
+        let end = inner.char_indices()
+            .find(|(_, c)| {
+                match c {
+                    '{' => depth += 1,
+                    '}' => depth -= 1,
+                    _ => {}
+                }
+                depth == 0
+            })
+            .map(|(i, _)| i)
+            .ok_or_else(|| error("unterminated block".into()))?;
+        let (id, content) = inner[..end].split_once(':')
+            .ok_or_else(|| error(format!("block '{}' has no identifier", &inner[..end])))?;
+        blocks.push((id.to_string(), content.to_string()));
+        rest = inner[end + 1..].trim_start();
+    }
+    Ok(blocks)
+}
+
+fn basic_header(content: &str) -> Result<Value, EnrichmentError> {
+    if content.len() != 25 || !content.is_ascii() {
+        return Err(error(format!("basic header '{}' must have 25 characters", content)));
+    }
+    Ok(json!({
+        "applicationId": &content[0..1],
+        "serviceId": &content[1..3],
+        "logicalTerminal": &content[3..15],
+        "session": &content[15..19],
+        "sequence": &content[19..25],
+    }))
+}
+
+fn application_header(content: &str) -> Result<Value, EnrichmentError> {
+    let invalid = || error(format!("application header '{}' is malformed", content));
+    if !content.is_ascii() {
+        return Err(invalid());
+    }
+    let optional = |start: usize, end: usize| content.get(start..end).filter(|s| !s.is_empty());
+    match content.get(0..1) {
+        Some("I") if (16..=21).contains(&content.len()) => Ok(json!({
+            "direction": "input",
+            "messageType": &content[1..4],
+            "receiver": &content[4..16],
+            "priority": optional(16, 17),
+            "deliveryMonitoring": optional(17, 18),
+            "obsolescencePeriod": optional(18, 21),
+        })),
+        Some("O") if (46..=47).contains(&content.len()) => Ok(json!({
+            "direction": "output",
+            "messageType": &content[1..4],
+            "inputTime": &content[4..8],
+            "inputDate": date(&content[8..14]).map_err(|_| invalid())?,
+            "sender": &content[14..26],
+            "session": &content[26..30],
+            "sequence": &content[30..36],
+            "outputDate": date(&content[36..42]).map_err(|_| invalid())?,
+            "outputTime": &content[42..46],
+            "priority": optional(46, 47),
+        })),
+        _ => Err(invalid()),
+    }
+}
+
+/// Tag and first line of a `:TAG:value` line, where the tag is two digits and an optional letter
+fn field_start(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix(':')?;
+    let (tag, value) = rest.split_once(':')?;
+    let bytes = tag.as_bytes();
+    let valid = matches!(bytes.len(), 2 | 3)
+        && bytes[..2].iter().all(u8::is_ascii_digit)
+        && bytes.get(2).is_none_or(u8::is_ascii_alphabetic);
+    valid.then_some((tag, value))
+}
+
+/// Split composite tags into named sub-fields, leaving other tags as text
+fn subfields(tag: &str, value: &str) -> Result<Value, String> {
+    match tag {
+        "32A" => {
+            let (date_part, rest) = split_at(value, 6)?;
+            let (currency, amount_part) = split_at(rest, 3)?;
+            Ok(json!({ "date": date(date_part)?, "currency": currency, "amount": amount(amount_part)? }))
+        }
+        "32B" | "33B" | "71F" | "71G" => {
+            let (currency, amount_part) = split_at(value, 3)?;
+            Ok(json!({ "currency": currency, "amount": amount(amount_part)? }))
+        }
+        "60F" | "60M" | "62F" | "62M" | "64" | "65" => {
+            let (mark, rest) = split_at(value, 1)?;
+            let (date_part, rest) = split_at(rest, 6)?;
+            let (currency, amount_part) = split_at(rest, 3)?;
+            Ok(json!({ "mark": mark, "date": date(date_part)?, "currency": currency, "amount": amount(amount_part)? }))
+        }
+        "61" => statement_line(value),
+        "50A" | "50F" | "50K" | "52A" | "53A" | "54A" | "56A" | "57A" | "59" | "59A" | "59F" => {
+            let (account, details) = match value.strip_prefix('/') {
+                Some(account) => match account.split_once('\n') {
+                    Some((account, details)) => (Some(account), details),
+                    None => (Some(account), ""),
+                },
+                None => (None, value),
+            };
+            let lines: Vec<&str> = details.lines().collect();
+            if tag.ends_with('A') {
+                Ok(json!({ "account": account, "bic": details.trim() }))
+            } else {
+                Ok(json!({ "account": account, "nameAndAddress": lines }))
+            }
+        }
+        _ => Ok(Value::String(value.to_string())),
+    }
+}
+
+/// Parse `6!n[4!n]2a[1!a]15d1!a3!c16x[//16x][34x]`
+fn statement_line(value: &str) -> Result<Value, String> {
+    let (first, supplementary) = match value.split_once('\n') {
+        Some((first, supplementary)) => (first, Some(supplementary)),
+        None => (value, None),
+    };
+    let (value_date_part, mut rest) = split_at(first, 6)?;
+    let value_date = NaiveDate::parse_from_str(value_date_part, "%y%m%d")
+        .map_err(|_| format!("has an invalid date '{}'", value_date_part))?;
+    let mut entry_date = None;
+    if rest.len() >= 4 && rest.as_bytes()[..4].iter().all(u8::is_ascii_digit) {
+        let (date_part, remaining) = split_at(rest, 4)?;
+        entry_date = Some(entry_date_near(date_part, value_date)?.format("%Y-%m-%d").to_string());
+        rest = remaining;
+    }
+    let mark_length = if rest.starts_with("RC") || rest.starts_with("RD") { 2 } else { 1 };
+    let (mark, mut rest) = split_at(rest, mark_length)?;
+    if !matches!(mark, "C" | "D" | "RC" | "RD") {
+        return Err(format!("has an invalid debit/credit mark '{}'", mark));
+    }
+    let mut funds_code = None;
+    if rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        let (code, remaining) = split_at(rest, 1)?;
+        funds_code = Some(code);
+        rest = remaining;
+    }
+    let amount_end = rest.find(|c: char| !c.is_ascii_digit() && c != ',').ok_or("has no transaction type")?;
+    let (amount_part, rest) = rest.split_at(amount_end);
+    let (transaction_type, rest) = split_at(rest, 4)?;
+    let (reference, bank_reference) = match rest.split_once("//") {
+        Some((reference, bank_reference)) => (reference, Some(bank_reference)),
+        None => (rest, None),
+    };
+
+    Ok(json!({
+        "valueDate": value_date.format("%Y-%m-%d").to_string(),
+        "entryDate": entry_date,
+        "mark": mark,
+        "fundsCode": funds_code,
+        "amount": amount(amount_part)?,
+        "transactionType": transaction_type,
+        "reference": reference,
+        "bankReference": bank_reference,
+        "supplementaryDetails": supplementary,
+    }))
+}
+
+fn split_at(value: &str, index: usize) -> Result<(&str, &str), String> {
+    if value.is_char_boundary(index) && value.len() >= index {
+        Ok(value.split_at(index))
+    } else {
+        Err("is too short".into())
+    }
+}
+
+/// Convert a `YYMMDD` date to `YYYY-MM-DD`
+fn date(value: &str) -> Result<String, String> {
+    NaiveDate::parse_from_str(value, "%y%m%d")
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .map_err(|_| format!("has an invalid date '{}'", value))
+}
+
+/// Resolve a `MMDD` entry date to the year closest to the value date, so an entry booked on
+/// 31 December for a value date of 2 January falls in the previous year
+fn entry_date_near(value: &str, value_date: NaiveDate) -> Result<NaiveDate, String> {
+    let invalid = || format!("has an invalid entry date '{}'", value);
+    let month_day: u32 = value.parse().map_err(|_| invalid())?;
+    [value_date.year() - 1, value_date.year(), value_date.year() + 1]
+        .into_iter()
+        .filter_map(|year| NaiveDate::from_ymd_opt(year, month_day / 100, month_day % 100))
+        .min_by_key(|date| (*date - value_date).num_days().abs())
+        .ok_or_else(invalid)
+}
+
+/// Convert an MT amount such as `1234,5` to `1234.5`
+fn amount(value: &str) -> Result<String, String> {
+    let valid = value.matches(',').count() == 1
+        && value.starts_with(|c: char| c.is_ascii_digit())
+        && value.chars().all(|c| c.is_ascii_digit() || c == ',');
+    if !valid {
+        return Err(format!("has an invalid amount '{}'", value));
+    }
+    Ok(value.replace(',', ".").trim_end_matches('.').to_string())
+}
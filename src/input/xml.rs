@@ -0,0 +1,149 @@
+use crate::error::EnrichmentError;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+
+/// Converts XML documents such as ISO 20022 messages into enrichment input.
+///
+/// The conversion follows these rules:
+/// - The root element becomes the single top-level key, so rules start at `$.Document`
+/// - Elements are keyed by local name; namespace prefixes are dropped and `xmlns` declarations
+///   are kept as attributes, so the namespace of every element can still be resolved
+/// - Attributes become `@name` keys, keeping any prefix, such as `@Ccy` or `@xsi:schemaLocation`
+/// - An element with neither attributes nor children becomes its text as a string, `""` when empty
+/// - Text of an element with attributes or children becomes a `#text` key; whitespace between
+///   child elements is dropped, and text interrupted by child elements is concatenated
+/// - Siblings sharing a name become an array in document order; names registered with
+///   [`XmlParser::with_array`] become arrays even when they occur once
+/// - Text is never converted to numbers or booleans, and CDATA sections count as text
+/// - Comments, processing instructions and the XML declaration are dropped
+///
+/// For ISO 20022 messages, whose schemas fix the order of differently named siblings and allow no
+/// mixed content, the tree keeps everything needed to reproduce the message.
+#[derive(Debug, Clone, Default)]
+pub struct XmlParser {
+    arrays: HashSet<String>,
+}
+
+/// Element being read, before it is converted to a value
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<(String, Value)>,
+    text: String,
+}
+
+impl XmlParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Always convert elements with this local name to arrays, such as `CdtTrfTxInf` in pain.001,
+    /// so rules can use `[*]` whether a message holds one transaction or many
+    pub fn with_array(mut self, name: impl Into<String>) -> Self {
+        self.arrays.insert(name.into());
+        self
+    }
+
+    pub fn parse_str(&self, xml: &str) -> Result<HashMap<String, Value>, EnrichmentError> {
+        self.parse_reader(xml.as_bytes())
+    }
+
+    pub fn parse_reader(&self, reader: impl BufRead) -> Result<HashMap<String, Value>, EnrichmentError> {
+        let mut reader = Reader::from_reader(reader);
+        reader.config_mut().expand_empty_elements = true;
+
+        let error = |message: String| EnrichmentError::DataTypeError(format!("Invalid XML: {}", message));
+        let mut buffer = Vec::new();
+        let mut stack: Vec<Element> = Vec::new();
+        let mut root: Option<(String, Value)> = None;
+
+        loop {
+            let position = reader.buffer_position();
+            let event = reader.read_event_into(&mut buffer)
+                .map_err(|e| error(format!("{} at byte {}", e, reader.error_position())))?;
+            match event {
+                Event::Start(start) => {
+                    if root.is_some() {
+                        return Err(error(format!("second root element at byte {}", position)));
+                    }
+                    stack.push(element(&start).map_err(error)?);
+                }
+                Event::End(_) => {
+                    let finished = stack.pop().expect("end tags are matched by the reader");
+                    let name = finished.name.clone();
+                    let value = self.convert(finished);
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push((name, value)),
+                        None => root = Some((name, value)),
+                    }
+                }
+                Event::Text(text) => {
+                    let text = text.unescape().map_err(|e| error(e.to_string()))?;
+                    match stack.last_mut() {
+                        Some(current) => current.text.push_str(&text),
+                        None if text.trim().is_empty() => {}
+                        None => return Err(error(format!("text outside the root element at byte {}", position))),
+                    }
+                }
+                Event::CData(data) => {
+                    let data = data.decode().map_err(|e| error(e.to_string()))?;
+                    match stack.last_mut() {
+                        Some(current) => current.text.push_str(&data),
+                        None => return Err(error(format!("CDATA outside the root element at byte {}", position))),
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buffer.clear();
+        }
+
+        if let Some(open) = stack.last() {
+            return Err(error(format!("element '{}' is never closed", open.name)));
+        }
+        let (name, value) = root.ok_or_else(|| error("no root element".into()))?;
+        Ok(HashMap::from([(name, value)]))
+    }
+
+    fn convert(&self, element: Element) -> Value {
+        if element.attributes.is_empty() && element.children.is_empty() {
+            return Value::String(element.text);
+        }
+
+        let mut object = Map::new();
+        for (name, value) in element.attributes {
+            object.insert(format!("@{}", name), Value::String(value));
+        }
+        for (name, value) in element.children {
+            match object.get_mut(&name) {
+                Some(Value::Array(values)) => values.push(value),
+                Some(existing) => *existing = Value::Array(vec![existing.take(), value]),
+                None if self.arrays.contains(&name) => {
+                    object.insert(name, Value::Array(vec![value]));
+                }
+                None => {
+                    object.insert(name, value);
+                }
+            }
+        }
+        if !element.text.trim().is_empty() {
+            object.insert("#text".into(), Value::String(element.text));
+        }
+        Value::Object(object)
+    }
+}
+
+fn element(start: &BytesStart) -> Result<Element, String> {
+    let name = String::from_utf8(start.local_name().as_ref().to_vec()).map_err(|e| e.to_string())?;
+    let mut attributes = Vec::new();
+    for attribute in start.attributes() {
+        let attribute = attribute.map_err(|e| e.to_string())?;
+        let key = String::from_utf8(attribute.key.as_ref().to_vec()).map_err(|e| e.to_string())?;
+        let value = attribute.unescape_value().map_err(|e| e.to_string())?;
+        attributes.push((key, value.into_owned()));
+    }
+    Ok(Element { name, attributes, children: Vec::new(), text: String::new() })
+}
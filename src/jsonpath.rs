@@ -15,13 +15,143 @@ impl JsonPathExtractor {
         let json_value = serde_json::to_value(data)
             .map_err(|e| EnrichmentError::JsonPathError(format!("Failed to convert data: {}", e)))?;
 
-        // Use jsonpath_lib with the converted Value
-        jsonpath_lib::select(&json_value, path)
+        Self::select(&json_value, path)
+    }
+
+    /// Extract the first value matching the path from an already converted document
+    pub fn select(data: &Value, path: &str) -> Result<Value, EnrichmentError> {
+        Self::select_optional(data, path)?
+            .ok_or_else(|| EnrichmentError::JsonPathError("Path not found".into()))
+    }
+
+    /// Extract the first value matching the path, or `None` when nothing matches
+    pub fn select_optional(data: &Value, path: &str) -> Result<Option<Value>, EnrichmentError> {
+        Ok(jsonpath_lib::select(data, path)
             .map_err(|e| EnrichmentError::JsonPathError(e.to_string()))?
             .first()
             .cloned()
-            .cloned()
-            .ok_or_else(|| EnrichmentError::JsonPathError("Path not found".into()))
+            .cloned())
+    }
+}
+
+/// Step of a target path
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Parse a target path made of `.name`, `['name']` and `[index]` steps after `$`;
+/// wildcards, filters and recursive descent select rather than address, so they are rejected
+pub(crate) fn parse_target(path: &str) -> Result<Vec<PathSegment>, EnrichmentError> {
+    let error = |reason: &str| EnrichmentError::SpecError(format!("Invalid target path '{}': {}", path, reason));
+    let mut rest = path.trim().strip_prefix('$').ok_or_else(|| error("must start with '$'"))?;
+    let mut segments = Vec::new();
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            let name = &after[..end];
+            if name.is_empty() || name == "*" {
+                return Err(error("expected a field name after '.'"));
+            }
+            segments.push(PathSegment::Key(name.to_string()));
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']').ok_or_else(|| error("unclosed '['"))?;
+            let inner = after[..end].trim();
+            let quoted = inner.strip_prefix('\'').and_then(|s| s.strip_suffix('\''))
+                .or_else(|| inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')));
+            match (quoted, inner.parse::<usize>()) {
+                (Some(name), _) => segments.push(PathSegment::Key(name.to_string())),
+                (None, Ok(index)) => segments.push(PathSegment::Index(index)),
+                (None, Err(_)) => return Err(error(&format!("'[{}]' does not address a single element", inner))),
+            }
+            rest = &after[end + 1..];
+        } else {
+            return Err(error(&format!("unexpected '{}'", rest)));
+        }
+    }
+    Ok(segments)
+}
+
+/// Most `null` elements a single write may add before the written index
+const MAX_ARRAY_PADDING: usize = 1024;
+
+/// Write a value at a target path, creating intermediate objects and arrays as needed.
+/// Arrays are padded with `null` up to the written index, at most [`MAX_ARRAY_PADDING`] elements.
+pub(crate) fn write(document: &mut Value, path: &str, value: Value) -> Result<(), EnrichmentError> {
+    let mut current = document;
+    for segment in parse_target(path)? {
+        if current.is_null() {
+            *current = match segment {
+                PathSegment::Key(_) => Value::Object(Default::default()),
+                PathSegment::Index(_) => Value::Array(Vec::new()),
+            };
+        }
+        current = match (segment, current) {
+            (PathSegment::Key(key), Value::Object(fields)) => fields.entry(key).or_insert(Value::Null),
+            (PathSegment::Index(index), Value::Array(values)) => {
+                if values.len() <= index {
+                    let padding = index - values.len();
+                    let length = index.checked_add(1).filter(|_| padding <= MAX_ARRAY_PADDING).ok_or_else(|| {
+                        EnrichmentError::SpecError(format!(
+                            "Invalid target path '{}': index {} is more than {} past the end of the array",
+                            path, index, MAX_ARRAY_PADDING
+                        ))
+                    })?;
+                    values.resize(length, Value::Null);
+                }
+                &mut values[index]
+            }
+            (segment, existing) => {
+                return Err(EnrichmentError::DataTypeError(format!(
+                    "Cannot write {} at '{}': found {}",
+                    match segment {
+                        PathSegment::Key(key) => format!("field '{}'", key),
+                        PathSegment::Index(index) => format!("index {}", index),
+                    },
+                    path,
+                    type_name(existing)
+                )))
+            }
+        };
+    }
+    *current = value;
+    Ok(())
+}
+
+/// Read the value at a target path, or `None` when any step is missing
+pub(crate) fn read<'v>(document: &'v Value, path: &str) -> Result<Option<&'v Value>, EnrichmentError> {
+    let mut current = document;
+    for segment in parse_target(path)? {
+        let next = match (segment, current) {
+            (PathSegment::Key(key), Value::Object(fields)) => fields.get(&key),
+            (PathSegment::Index(index), Value::Array(values)) => values.get(index),
+            _ => None,
+        };
+        match next {
+            Some(value) => current = value,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(current))
+}
+
+pub(crate) fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+impl Default for JsonPathExtractor {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
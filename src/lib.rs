@@ -3,9 +3,47 @@ mod types;
 mod transform;
 mod traits;
 mod jsonpath;
+mod engine;
+mod provider;
+mod currency;
+mod country;
+mod lookup;
+mod input;
+#[cfg(feature = "schemars")]
+mod schema;
 
 pub use error::EnrichmentError;
-pub use types::{MappingRule, JsonPath, Target, Transform, TransformType};
+pub use types::{MappingRule, JsonPath, NowSource, Spec, Target, Transform, UuidSource, WriteMode};
+pub use transform::{
+    AddBusinessDaysParams, AddDaysParams, ArithmeticParams, AtParams, Base64Params, BicParams, CaptureGroup,
+    ConcatParams, ConvertTimezoneParams, CurrencyParam, DateDiffParams, DateUnit, FilterParams, FlattenParams,
+    FormatDateParams, FormatMoneyParams, HashParams, HexParams, IbanComponent, IbanComponentsParams, IbanFormat,
+    IbanParams, KeysParams, LookupParams, MajorUnitsParams, MapParams, MaskParams, MaskPreset, MergeParams,
+    MinorUnitsParams, NormalizationForm, NormalizeParams, OnMissing, Operand, PadParams, ParseDateParams,
+    ParseJsonParams, ParseMoneyParams, Pattern, RedactParams, RegexCapturesParams, RegexExtractParams,
+    RegexMatchParams, RegexReplaceParams, ReplaceParams, RoundParams, RoundingMode, ScaleParams, SliceParams,
+    SortParams, SplitParams, StringifyJsonParams, SubstringParams, SumMoneyParams, TableSource, Template,
+    TemplateParams, ToBooleanParams, ToIntegerParams, ToNullParams, TokenizeParams, TrimParams, TruncateParams,
+};
 pub use traits::{Enrichable, Validatable, ValueSerializer, PathExtractor};
 pub use jsonpath::JsonPathExtractor;
+pub use engine::{transform_value, Engine};
+pub use lookup::LookupTable;
+pub use input::{CsvParser, CsvRecords, FixedWidthColumn, FixedWidthLayout, FixedWidthRecords, MtParser};
+pub use provider::{
+    Clock, FixedClock, InMemoryTokenVault, RandomUuidGenerator, SequentialUuidGenerator, SystemClock, TokenVault,
+    UuidGenerator,
+};
+#[cfg(feature = "xml")]
+pub use input::XmlParser;
+#[cfg(feature = "schemars")]
+pub use schema::spec_schema;
 pub use enrichr_derive::Enrichable;
+/// Re-exported for the code the derive macro generates, so callers need no direct dependency
+pub use serde_json;
+
+/// Items used by the code the derive macro generates
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::traits::{FieldReader, ReadSerialize, ReadUnserializable};
+}
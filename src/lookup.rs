@@ -0,0 +1,80 @@
+use crate::error::EnrichmentError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Table mapping input codes to output values for `lookup` transforms
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct LookupTable {
+    entries: HashMap<String, Value>,
+}
+
+impl LookupTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, value: Value) {
+        self.entries.insert(key.into(), value);
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.entries.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Read a JSON object whose keys are the input codes
+    pub fn from_json_reader(reader: impl Read) -> Result<Self, EnrichmentError> {
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    /// Read a CSV file with a header row, taking keys from the first column and values from the second
+    pub fn from_csv_reader(reader: impl Read) -> Result<Self, EnrichmentError> {
+        let mut table = Self::new();
+        let mut csv = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(reader);
+        for (line, record) in csv.records().enumerate() {
+            let record = record.map_err(|e| EnrichmentError::SpecError(format!("Invalid lookup table CSV: {}", e)))?;
+            match (record.get(0), record.get(1)) {
+                (Some(key), Some(value)) => table.insert(key, Value::String(value.into())),
+                _ => {
+                    return Err(EnrichmentError::SpecError(format!(
+                        "Lookup table row {} needs a key and a value column", line + 2
+                    )))
+                }
+            }
+        }
+        Ok(table)
+    }
+
+    /// Load a table from a `.json` or `.csv` file, chosen by its extension
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, EnrichmentError> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .map_err(|e| EnrichmentError::SpecError(format!("Cannot read lookup table {}: {}", path.display(), e)))?;
+        match path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase).as_deref() {
+            Some("json") => Self::from_json_reader(file),
+            Some("csv") => Self::from_csv_reader(file),
+            _ => Err(EnrichmentError::SpecError(format!(
+                "Lookup table {} must be a .json or .csv file", path.display()
+            ))),
+        }
+    }
+}
+
+impl<K: Into<String>> FromIterator<(K, Value)> for LookupTable {
+    fn from_iter<I: IntoIterator<Item = (K, Value)>>(iter: I) -> Self {
+        Self { entries: iter.into_iter().map(|(key, value)| (key.into(), value)).collect() }
+    }
+}
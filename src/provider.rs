@@ -0,0 +1,116 @@
+use crate::error::EnrichmentError;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Source of the current time for `now` rule sources
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Source of identifiers for `uuid` rule sources
+pub trait UuidGenerator: Send + Sync {
+    fn generate(&self) -> Uuid;
+}
+
+/// Store exchanging sensitive values for surrogate tokens in `tokenize` transforms.
+/// Implementations must return the same token whenever the same value is tokenized in a namespace.
+pub trait TokenVault: Send + Sync {
+    fn tokenize(&self, namespace: &str, value: &str) -> Result<String, EnrichmentError>;
+
+    /// Look up the original value of a token, or `None` if the vault never issued it
+    fn detokenize(&self, namespace: &str, token: &str) -> Result<Option<String>, EnrichmentError>;
+}
+
+/// Clock reading the system time
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock that always returns the same instant, for deterministic output in tests
+pub struct FixedClock {
+    instant: DateTime<Utc>,
+}
+
+impl FixedClock {
+    pub fn new(instant: DateTime<Utc>) -> Self {
+        Self { instant }
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.instant
+    }
+}
+
+/// Generator producing random version 4 UUIDs
+pub struct RandomUuidGenerator;
+
+impl UuidGenerator for RandomUuidGenerator {
+    fn generate(&self) -> Uuid {
+        Uuid::new_v4()
+    }
+}
+
+/// Generator producing UUIDs from an incrementing counter, for deterministic output in tests
+pub struct SequentialUuidGenerator {
+    next: Mutex<u128>,
+}
+
+impl SequentialUuidGenerator {
+    pub fn new(start: u128) -> Self {
+        Self { next: Mutex::new(start) }
+    }
+}
+
+impl UuidGenerator for SequentialUuidGenerator {
+    fn generate(&self) -> Uuid {
+        let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+        let id = *next;
+        *next = next.wrapping_add(1);
+        Uuid::from_u128(id)
+    }
+}
+
+/// Token vault kept in memory, issuing `tok_`-prefixed sequential tokens, for tests and single-process use
+#[derive(Default)]
+pub struct InMemoryTokenVault {
+    entries: Mutex<VaultEntries>,
+}
+
+#[derive(Default)]
+struct VaultEntries {
+    tokens: HashMap<(String, String), String>,
+    values: HashMap<(String, String), String>,
+}
+
+impl InMemoryTokenVault {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TokenVault for InMemoryTokenVault {
+    fn tokenize(&self, namespace: &str, value: &str) -> Result<String, EnrichmentError> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let key = (namespace.to_string(), value.to_string());
+        if let Some(token) = entries.tokens.get(&key) {
+            return Ok(token.clone());
+        }
+        let token = format!("tok_{:012}", entries.tokens.len() + 1);
+        entries.values.insert((namespace.to_string(), token.clone()), value.to_string());
+        entries.tokens.insert(key, token.clone());
+        Ok(token)
+    }
+
+    fn detokenize(&self, namespace: &str, token: &str) -> Result<Option<String>, EnrichmentError> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(entries.values.get(&(namespace.to_string(), token.to_string())).cloned())
+    }
+}
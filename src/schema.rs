@@ -0,0 +1,97 @@
+use crate::types::{MappingRule, Spec, TRANSFORM_NAMES};
+use schemars::generate::SchemaSettings;
+use schemars::{JsonSchema, Schema};
+use serde_json::{json, Value};
+
+/// A mapping spec file: either a spec with vars and rules, or a bare list of rules
+#[derive(JsonSchema)]
+#[serde(untagged)]
+#[allow(dead_code)]
+enum SpecFile {
+    Spec(Spec),
+    Rules(Vec<MappingRule>),
+}
+
+/// Allow each transform variant's params to be nested under `params` as well as inline
+pub(crate) fn accept_nested_params(schema: &mut Schema) {
+    let Some(variants) = schema.get_mut("oneOf").and_then(Value::as_array_mut) else {
+        return;
+    };
+
+    for variant in variants.iter_mut() {
+        let Some(properties) = variant.get("properties").and_then(Value::as_object) else {
+            continue;
+        };
+
+        let mut params = properties.clone();
+        let tag = params.remove("type").map(accept_aliases);
+        if let Some(tag) = &tag {
+            variant["properties"]["type"] = tag.clone();
+        }
+        let required = variant.get("required")
+            .and_then(Value::as_array)
+            .map(|names| names.iter().filter(|name| *name != "type").cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        let mut nested = json!({
+            "type": "object",
+            "properties": {
+                "type": tag,
+                "params": {
+                    "type": "object",
+                    "properties": params,
+                    "required": required,
+                    "additionalProperties": false
+                }
+            },
+            "required": ["type"],
+            "additionalProperties": false
+        });
+        if !required.is_empty() {
+            nested["required"] = json!(["type", "params"]);
+        }
+
+        *variant = json!({ "anyOf": [variant.take(), nested] });
+    }
+}
+
+/// Widen a transform's `type` constant to every name the deserializer accepts: the canonical name
+/// and its aliases, in any letter case and with `_` or `-` between letters
+fn accept_aliases(mut tag: Value) -> Value {
+    let Some((canonical, aliases)) = tag.get("const")
+        .and_then(Value::as_str)
+        .and_then(|name| TRANSFORM_NAMES.iter().find(|(canonical, _)| *canonical == name))
+    else {
+        return tag;
+    };
+
+    let names: Vec<&str> = std::iter::once(*canonical).chain(aliases.iter().copied()).collect();
+    let alternatives: Vec<String> = names.iter()
+        .map(|name| {
+            name.chars()
+                .filter(char::is_ascii_alphanumeric)
+                .map(|c| if c.is_ascii_alphabetic() {
+                    format!("[{}{}]", c.to_ascii_uppercase(), c.to_ascii_lowercase())
+                } else {
+                    c.to_string()
+                })
+                .collect::<Vec<_>>()
+                .join("[_-]*")
+        })
+        .collect();
+
+    tag.as_object_mut().expect("tag schema is an object").remove("const");
+    tag["pattern"] = format!("^[_-]*(?:{})[_-]*$", alternatives.join("|")).into();
+    tag["examples"] = json!(names);
+    tag
+}
+
+/// Generate the JSON Schema describing mapping spec files
+pub fn spec_schema() -> serde_json::Value {
+    let schema = SchemaSettings::draft07()
+        .into_generator()
+        .into_root_schema_for::<SpecFile>();
+    let mut value = schema.to_value();
+    value["title"] = "enrichr mapping spec".into();
+    value
+}
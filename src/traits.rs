@@ -1,11 +1,57 @@
+use crate::engine::Engine;
 use crate::error::EnrichmentError;
-use crate::types::MappingRule;
+use crate::types::{MappingRule, Spec};
+use serde::Serialize;
 use std::collections::HashMap;
 
 /// Main trait for types that can be enriched with data from external sources
 pub trait Enrichable {
     /// Enrich the implementing type with data according to the provided rules
-    fn enrich(&mut self, data: &HashMap<String, serde_json::Value>, rules: &[MappingRule]) -> Result<(), EnrichmentError>;
+    fn enrich(&mut self, data: &HashMap<String, serde_json::Value>, rules: &[MappingRule]) -> Result<(), EnrichmentError> {
+        let root = serde_json::Value::Object(data.clone().into_iter().collect());
+        Engine::new().apply_rules(self, &root, rules, &HashMap::new())
+    }
+
+    /// Enrich from any JSON document, so rules can address array or scalar roots as `$[0]` or `$`
+    fn enrich_value(&mut self, data: &serde_json::Value, rules: &[MappingRule]) -> Result<(), EnrichmentError> {
+        Engine::new().apply_rules(self, data, rules, &HashMap::new())
+    }
+
+    /// Enrich from any serializable source, such as another domain struct, addressed by its serialized form
+    fn enrich_from<S>(&mut self, source: &S, rules: &[MappingRule]) -> Result<(), EnrichmentError>
+    where
+        Self: Sized,
+        S: Serialize + ?Sized,
+    {
+        self.enrich_value(&serde_json::to_value(source)?, rules)
+    }
+
+    /// Enrich the implementing type according to a spec, resolving variables against the engine's context
+    fn enrich_with(&mut self, data: &HashMap<String, serde_json::Value>, spec: &Spec, engine: &Engine) -> Result<(), EnrichmentError> {
+        engine.enrich(self, data, spec)
+    }
+
+    /// Enrich from any JSON document according to a spec
+    fn enrich_value_with(&mut self, data: &serde_json::Value, spec: &Spec, engine: &Engine) -> Result<(), EnrichmentError> {
+        engine.enrich_value(self, data, spec)
+    }
+
+    /// Enrich from any serializable source according to a spec
+    fn enrich_from_with<S>(&mut self, source: &S, spec: &Spec, engine: &Engine) -> Result<(), EnrichmentError>
+    where
+        Self: Sized,
+        S: Serialize + ?Sized,
+    {
+        engine.enrich_from(self, source, spec)
+    }
+
+    /// Set a single field from a JSON value
+    fn set_field(&mut self, field: &str, value: serde_json::Value) -> Result<(), EnrichmentError>;
+
+    /// Read a single field as a JSON value, needed by rules whose write mode combines with the current value
+    fn get_field(&self, field: &str) -> Result<serde_json::Value, EnrichmentError> {
+        Err(EnrichmentError::SpecError(format!("Field {} cannot be read; derive Enrichable or implement get_field", field)))
+    }
 }
 
 /// Trait for types that can be validated before enrichment
@@ -30,3 +76,34 @@ pub trait PathExtractor {
     /// Extract multiple values from data using multiple paths
     fn extract_values(&self, data: &HashMap<String, serde_json::Value>, paths: &[String]) -> Result<Vec<serde_json::Value>, EnrichmentError>;
 }
+
+/// Field access for the derive macro, reading through `Serialize` when the field's type implements it.
+/// Method resolution prefers [`ReadSerialize`] and falls back to [`ReadUnserializable`] through autoref,
+/// so structs with fields that only implement `Deserialize` still derive `Enrichable`.
+#[doc(hidden)]
+pub struct FieldReader<'a, T>(pub &'a T, pub &'a str);
+
+#[doc(hidden)]
+pub trait ReadSerialize {
+    fn read_field(&self) -> Result<serde_json::Value, EnrichmentError>;
+}
+
+impl<T: Serialize> ReadSerialize for FieldReader<'_, T> {
+    fn read_field(&self) -> Result<serde_json::Value, EnrichmentError> {
+        serde_json::to_value(self.0).map_err(|e| EnrichmentError::DataTypeError(e.to_string()))
+    }
+}
+
+#[doc(hidden)]
+pub trait ReadUnserializable {
+    fn read_field(&self) -> Result<serde_json::Value, EnrichmentError>;
+}
+
+impl<T> ReadUnserializable for &FieldReader<'_, T> {
+    fn read_field(&self) -> Result<serde_json::Value, EnrichmentError> {
+        Err(EnrichmentError::SpecError(format!(
+            "Field {} cannot be read because its type does not implement Serialize, which write modes other than overwrite need",
+            self.1
+        )))
+    }
+}
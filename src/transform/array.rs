@@ -1,28 +1,285 @@
+use super::numeric::{decimal_to_value, number_to_decimal, parse_decimal};
 use super::Transformer;
+use crate::engine::Scope;
 use crate::error::EnrichmentError;
+use crate::jsonpath::JsonPathExtractor;
+use crate::types::Transform;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashSet;
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct SplitParams {
+    /// Separator between the parts of the string
+    pub delimiter: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ConcatParams {
+    /// Separator inserted between the joined values
+    #[serde(default)]
+    pub delimiter: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct AtParams {
+    /// Position of the element, counting from the end when negative
+    pub index: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct SliceParams {
+    /// First position to keep, counting from the end when negative
+    #[serde(default)]
+    pub start: i64,
+    /// Position to stop before, defaulting to the end of the array
+    pub end: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct FilterParams {
+    /// JSONLogic predicate evaluated with each element as its data
+    pub condition: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct MapParams {
+    /// Transforms applied in order to every element
+    pub transforms: Vec<Transform>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct SortParams {
+    /// JSONPath within each element giving its sort key, defaulting to the element itself
+    pub by: Option<String>,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct FlattenParams {
+    /// Number of nesting levels to remove
+    #[serde(default = "default_depth")]
+    pub depth: usize,
+}
+
+fn default_depth() -> usize {
+    1
+}
+
+pub enum ArrayOperation {
+    Split(String),
+    First,
+    Last,
+    At(i64),
+    Slice { start: i64, end: Option<i64> },
+    Filter(Value),
+    Sort { by: Option<String>, descending: bool },
+    Unique,
+    Flatten(usize),
+    Count,
+    Sum,
+}
+
+/// Array transforms; `split` turns a string into an array and the others reshape or reduce arrays
 pub struct ArrayTransformer {
-    delimiter: String,
+    operation: ArrayOperation,
 }
 
 impl ArrayTransformer {
+    pub fn new(operation: ArrayOperation) -> Self {
+        Self { operation }
+    }
+}
+
+impl Transformer for ArrayTransformer {
+    fn transform(&self, value: &Value) -> Result<Value, EnrichmentError> {
+        // Every operation except split needs an array input
+        let values = value.as_array()
+            .ok_or_else(|| EnrichmentError::TransformError("Value is not an array".into()));
+
+        match &self.operation {
+            ArrayOperation::Split(delimiter) => match value {
+                Value::String(s) => {
+                    let parts: Vec<Value> = s
+                        .split(delimiter.as_str())
+                        .map(|part| Value::String(part.to_string()))
+                        .collect();
+                    Ok(Value::Array(parts))
+                }
+                _ => Err(EnrichmentError::TransformError("Value is not a string".into())),
+            },
+            ArrayOperation::First => Ok(values?.first().cloned().unwrap_or(Value::Null)),
+            ArrayOperation::Last => Ok(values?.last().cloned().unwrap_or(Value::Null)),
+            ArrayOperation::At(index) => {
+                let values = values?;
+                Ok(position(*index, values.len())
+                    .and_then(|i| values.get(i))
+                    .cloned()
+                    .unwrap_or(Value::Null))
+            }
+            ArrayOperation::Slice { start, end } => {
+                let values = values?;
+                let start = position(*start, values.len()).unwrap_or(if *start < 0 { 0 } else { values.len() });
+                let end = match end {
+                    Some(end) => position(*end, values.len()).unwrap_or(if *end < 0 { 0 } else { values.len() }),
+                    None => values.len(),
+                };
+                Ok(Value::Array(values.get(start..end.max(start)).unwrap_or_default().to_vec()))
+            }
+            ArrayOperation::Filter(condition) => {
+                let mut kept = Vec::new();
+                for element in values? {
+                    let result = jsonlogic_rs::apply(condition, element)
+                        .map_err(|e| EnrichmentError::TransformError(format!("Filter condition failed: {}", e)))?;
+                    if truthy(&result) {
+                        kept.push(element.clone());
+                    }
+                }
+                Ok(Value::Array(kept))
+            }
+            ArrayOperation::Sort { by, descending } => {
+                let mut keyed = values?.iter()
+                    .map(|element| {
+                        let key = match by {
+                            Some(path) => JsonPathExtractor::select_optional(element, path)?.unwrap_or(Value::Null),
+                            None => element.clone(),
+                        };
+                        Ok((SortKey::new(&key)?, element.clone()))
+                    })
+                    .collect::<Result<Vec<_>, EnrichmentError>>()?;
+                keyed.sort_by(|(a, _), (b, _)| if *descending { b.cmp(a) } else { a.cmp(b) });
+                Ok(Value::Array(keyed.into_iter().map(|(_, element)| element).collect()))
+            }
+            ArrayOperation::Unique => {
+                let mut seen = HashSet::new();
+                let unique = values?.iter()
+                    .filter(|element| seen.insert(element.to_string()))
+                    .cloned()
+                    .collect();
+                Ok(Value::Array(unique))
+            }
+            ArrayOperation::Flatten(depth) => Ok(Value::Array(flatten(values?, *depth))),
+            ArrayOperation::Count => Ok(Value::from(values?.len())),
+            ArrayOperation::Sum => {
+                let total = values?.iter().try_fold(Decimal::ZERO, |total, element| {
+                    let number = match element {
+                        Value::Number(n) => number_to_decimal(n)?,
+                        Value::String(s) => parse_decimal(s.trim())?,
+                        _ => return Err(EnrichmentError::TransformError(format!("Cannot sum {}", element))),
+                    };
+                    total.checked_add(number)
+                        .ok_or_else(|| EnrichmentError::TransformError("Numeric overflow in sum".into()))
+                })?;
+                decimal_to_value(total)
+            }
+        }
+    }
+}
+
+/// Resolve a possibly negative index against the array length
+fn position(index: i64, len: usize) -> Option<usize> {
+    let index = if index < 0 { len as i64 + index } else { index };
+    usize::try_from(index).ok().filter(|i| *i <= len)
+}
+
+/// JSONLogic truthiness: `false`, `null`, `0`, `""` and `[]` are false
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(values) => !values.is_empty(),
+        Value::Object(_) => true,
+    }
+}
+
+fn flatten(values: &[Value], depth: usize) -> Vec<Value> {
+    let mut result = Vec::new();
+    for value in values {
+        match value {
+            Value::Array(inner) if depth > 0 => result.extend(flatten(inner, depth - 1)),
+            other => result.push(other.clone()),
+        }
+    }
+    result
+}
+
+/// Sort key ordering nulls, then booleans, then numbers, then strings
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum SortKey {
+    Null,
+    Bool(bool),
+    Number(Decimal),
+    Text(String),
+}
+
+impl SortKey {
+    fn new(value: &Value) -> Result<Self, EnrichmentError> {
+        match value {
+            Value::Null => Ok(SortKey::Null),
+            Value::Bool(b) => Ok(SortKey::Bool(*b)),
+            Value::Number(n) => Ok(SortKey::Number(number_to_decimal(n)?)),
+            Value::String(s) => Ok(SortKey::Text(s.clone())),
+            _ => Err(EnrichmentError::TransformError(format!("Cannot sort by {}", value))),
+        }
+    }
+}
+
+/// Apply a pipeline of transforms to every element of an array
+pub(crate) fn map(transforms: &[Transform], value: &Value, scope: &Scope) -> Result<Value, EnrichmentError> {
+    let values = value.as_array()
+        .ok_or_else(|| EnrichmentError::TransformError("Value is not an array".into()))?;
+    values.iter()
+        .map(|element| {
+            transforms.iter().try_fold(element.clone(), |current, transform| super::apply(transform, &current, scope))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Value::Array)
+}
+
+pub struct ConcatTransformer {
+    delimiter: String,
+}
+
+impl ConcatTransformer {
     pub fn new(delimiter: String) -> Self {
         Self { delimiter }
     }
 }
 
-impl Transformer for ArrayTransformer {
+impl Transformer for ConcatTransformer {
     fn transform(&self, value: &Value) -> Result<Value, EnrichmentError> {
         match value {
-            Value::String(s) => {
-                let parts: Vec<Value> = s
-                    .split(&self.delimiter)
-                    .map(|part| Value::String(part.to_string()))
-                    .collect();
-                Ok(Value::Array(parts))
+            Value::Array(values) => {
+                let parts = values.iter()
+                    .map(|val| match val {
+                        Value::String(s) => Ok(s.clone()),
+                        Value::Number(_) | Value::Bool(_) => Ok(val.to_string()),
+                        _ => Err(EnrichmentError::TransformError("Array value is not a scalar".into())),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Value::String(parts.join(&self.delimiter)))
             }
-            _ => Err(EnrichmentError::TransformError("Value is not a string".into())),
+            _ => Err(EnrichmentError::TransformError("Value is not an array".into())),
         }
     }
 }
@@ -0,0 +1,118 @@
+use super::Transformer;
+use crate::error::EnrichmentError;
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ToBooleanParams {
+    /// Spellings read as `true`
+    #[serde(default = "default_truthy")]
+    pub truthy: Vec<String>,
+    /// Spellings read as `false`
+    #[serde(default = "default_falsy")]
+    pub falsy: Vec<String>,
+    /// Compare spellings exactly instead of ignoring case
+    #[serde(default)]
+    pub case_sensitive: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ToNullParams {
+    /// Values replaced by `null`; strings are compared after trimming whitespace
+    #[serde(default = "default_null_values")]
+    pub values: Vec<Value>,
+}
+
+fn default_truthy() -> Vec<String> {
+    ["true", "yes", "y", "1", "on"].map(String::from).to_vec()
+}
+
+fn default_falsy() -> Vec<String> {
+    ["false", "no", "n", "0", "off"].map(String::from).to_vec()
+}
+
+fn default_null_values() -> Vec<Value> {
+    vec![Value::String(String::new())]
+}
+
+pub enum CoerceOperation {
+    ToBoolean { truthy: Vec<String>, falsy: Vec<String>, case_sensitive: bool },
+    ToNull(Vec<Value>),
+}
+
+pub struct CoerceTransformer {
+    operation: CoerceOperation,
+}
+
+impl CoerceTransformer {
+    pub fn new(operation: CoerceOperation) -> Self {
+        Self { operation }
+    }
+}
+
+impl Transformer for CoerceTransformer {
+    fn transform(&self, value: &Value) -> Result<Value, EnrichmentError> {
+        match &self.operation {
+            CoerceOperation::ToBoolean { truthy, falsy, case_sensitive } => {
+                let text = match value {
+                    Value::Bool(_) => return Ok(value.clone()),
+                    Value::String(s) => s.trim().to_string(),
+                    Value::Number(n) => n.to_string(),
+                    _ => return Err(EnrichmentError::TransformError(format!("Cannot convert {} to a boolean", value))),
+                };
+                let matches = |spelling: &String| {
+                    if *case_sensitive {
+                        *spelling == text
+                    } else {
+                        spelling.eq_ignore_ascii_case(&text)
+                    }
+                };
+                if truthy.iter().any(matches) {
+                    Ok(Value::Bool(true))
+                } else if falsy.iter().any(matches) {
+                    Ok(Value::Bool(false))
+                } else {
+                    Err(EnrichmentError::TransformError(format!("'{}' is not a recognized boolean", text)))
+                }
+            }
+            CoerceOperation::ToNull(values) => {
+                let is_null = values.iter().any(|candidate| match (candidate, value) {
+                    (Value::String(candidate), Value::String(s)) => candidate.trim() == s.trim(),
+                    (candidate, value) => candidate == value,
+                });
+                Ok(if is_null { Value::Null } else { value.clone() })
+            }
+        }
+    }
+}
+
+/// Alternative representations of a value tried, in order, when it does not fit the target field's type
+pub(crate) fn coercions(value: &Value) -> Vec<Value> {
+    match value {
+        Value::String(s) => {
+            let s = s.trim();
+            let mut candidates = Vec::new();
+            if let Ok(i) = i64::from_str(s) {
+                candidates.push(Value::from(i));
+            } else if let Ok(u) = u64::from_str(s) {
+                candidates.push(Value::from(u));
+            } else if let Ok(n) = Number::from_str(s) {
+                candidates.push(Value::Number(n));
+            } else if let Some(n) = f64::from_str(s).ok().and_then(Number::from_f64) {
+                candidates.push(Value::Number(n));
+            }
+            if s.eq_ignore_ascii_case("true") || s.eq_ignore_ascii_case("false") {
+                candidates.push(Value::Bool(s.eq_ignore_ascii_case("true")));
+            }
+            candidates
+        }
+        Value::Number(n) => vec![Value::String(n.to_string())],
+        Value::Bool(b) => vec![Value::String(b.to_string())],
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,301 @@
+use super::Transformer;
+use crate::error::EnrichmentError;
+use crate::types::JsonPath;
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ParseDateParams {
+    /// A strftime pattern, `rfc3339`, `epochSeconds` or `epochMillis`
+    pub format: String,
+    /// IANA time zone of inputs without an offset, defaulting to UTC for date-times
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FormatDateParams {
+    /// A strftime pattern, `rfc3339`, `epochSeconds` or `epochMillis`
+    pub format: String,
+    /// IANA time zone to convert date-times to before formatting
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ConvertTimezoneParams {
+    /// IANA time zone name such as `Europe/Berlin`
+    pub timezone: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AddDaysParams {
+    /// Number of calendar days to add; negative values subtract
+    pub days: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AddBusinessDaysParams {
+    /// Number of business days to add; negative values subtract
+    pub days: i64,
+    /// Dates skipped in addition to weekends
+    #[serde(default)]
+    pub holidays: Vec<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DateDiffParams {
+    /// Source of the date to measure to, e.g. `"$.settlement_date"` or `{"now": {}}`
+    pub to: JsonPath,
+    #[serde(default)]
+    pub unit: DateUnit,
+    /// Dates skipped in addition to weekends when counting business days
+    #[serde(default)]
+    pub holidays: Vec<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum DateUnit {
+    #[default]
+    Days,
+    BusinessDays,
+    Hours,
+    Minutes,
+    Seconds,
+    Milliseconds,
+}
+
+/// A date as it flows between rules: `YYYY-MM-DD` for dates, RFC 3339 for date-times
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateValue {
+    Date(NaiveDate),
+    DateTime(DateTime<FixedOffset>),
+}
+
+impl DateValue {
+    /// Read an RFC 3339 date-time, an ISO 8601 date-time without offset (taken as UTC) or an ISO 8601 date
+    pub fn from_value(value: &Value) -> Result<Self, EnrichmentError> {
+        let s = value.as_str()
+            .ok_or_else(|| EnrichmentError::TransformError("Value is not a date string".into()))?;
+        if let Ok(datetime) = DateTime::parse_from_rfc3339(s) {
+            return Ok(DateValue::DateTime(datetime));
+        }
+        if let Ok(naive) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f") {
+            return Ok(DateValue::DateTime(naive.and_utc().fixed_offset()));
+        }
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map(DateValue::Date)
+            .map_err(|_| EnrichmentError::TransformError(format!("'{}' is not an ISO 8601 date or date-time", s)))
+    }
+
+    pub fn to_value(self) -> Value {
+        match self {
+            DateValue::Date(date) => Value::String(date.format("%Y-%m-%d").to_string()),
+            DateValue::DateTime(datetime) => Value::String(datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+        }
+    }
+
+    fn to_datetime(self) -> DateTime<FixedOffset> {
+        match self {
+            DateValue::Date(date) => date.and_time(Default::default()).and_utc().fixed_offset(),
+            DateValue::DateTime(datetime) => datetime,
+        }
+    }
+
+    fn date(self) -> NaiveDate {
+        match self {
+            DateValue::Date(date) => date,
+            DateValue::DateTime(datetime) => datetime.date_naive(),
+        }
+    }
+
+    /// Move to another calendar day, keeping the time of day and offset of date-times
+    fn with_date(self, date: NaiveDate) -> Result<Self, EnrichmentError> {
+        match self {
+            DateValue::Date(_) => Ok(DateValue::Date(date)),
+            DateValue::DateTime(datetime) => datetime.checked_add_signed(date - datetime.date_naive())
+                .map(DateValue::DateTime)
+                .ok_or_else(|| EnrichmentError::TransformError("Date out of range".into())),
+        }
+    }
+}
+
+/// Format a date with a strftime pattern, `rfc3339`, `epochSeconds` or `epochMillis`
+pub(crate) fn format_date(value: DateValue, format: &str) -> Result<Value, EnrichmentError> {
+    match format {
+        "rfc3339" => Ok(value.to_value()),
+        "epochSeconds" => Ok(Value::from(value.to_datetime().timestamp())),
+        "epochMillis" => Ok(Value::from(value.to_datetime().timestamp_millis())),
+        pattern => {
+            let mut formatted = String::new();
+            let result = match value {
+                DateValue::Date(date) => write!(formatted, "{}", date.format(pattern)),
+                DateValue::DateTime(datetime) => write!(formatted, "{}", datetime.format(pattern)),
+            };
+            result.map_err(|_| EnrichmentError::TransformError(format!("Invalid date format: {}", pattern)))?;
+            Ok(Value::String(formatted))
+        }
+    }
+}
+
+pub(crate) fn parse_timezone(name: &str) -> Result<Tz, EnrichmentError> {
+    name.parse::<Tz>()
+        .map_err(|_| EnrichmentError::SpecError(format!("Unknown time zone: {}", name)))
+}
+
+pub enum DateOperation {
+    Parse { format: String, timezone: Option<Tz> },
+    Format { format: String, timezone: Option<Tz> },
+    ConvertTimezone(Tz),
+    AddDays(i64),
+    AddBusinessDays { days: i64, holidays: Vec<NaiveDate> },
+    Diff { to: DateValue, unit: DateUnit, holidays: Vec<NaiveDate> },
+}
+
+pub struct DateTransformer {
+    operation: DateOperation,
+}
+
+impl DateTransformer {
+    pub fn new(operation: DateOperation) -> Self {
+        Self { operation }
+    }
+}
+
+impl Transformer for DateTransformer {
+    fn transform(&self, value: &Value) -> Result<Value, EnrichmentError> {
+        match &self.operation {
+            DateOperation::Parse { format, timezone } => parse(value, format, *timezone).map(DateValue::to_value),
+            DateOperation::Format { format, timezone } => {
+                let date = match (DateValue::from_value(value)?, timezone) {
+                    (DateValue::DateTime(datetime), Some(tz)) => DateValue::DateTime(datetime.with_timezone(tz).fixed_offset()),
+                    (date, _) => date,
+                };
+                format_date(date, format)
+            }
+            DateOperation::ConvertTimezone(tz) => match DateValue::from_value(value)? {
+                DateValue::DateTime(datetime) => Ok(DateValue::DateTime(datetime.with_timezone(tz).fixed_offset()).to_value()),
+                DateValue::Date(_) => Err(EnrichmentError::TransformError("Cannot convert the time zone of a date without time".into())),
+            },
+            DateOperation::AddDays(days) => {
+                let date = DateValue::from_value(value)?;
+                let shifted = Duration::try_days(*days)
+                    .and_then(|days| date.date().checked_add_signed(days))
+                    .ok_or_else(|| EnrichmentError::TransformError("Date out of range".into()))?;
+                Ok(date.with_date(shifted)?.to_value())
+            }
+            DateOperation::AddBusinessDays { days, holidays } => {
+                let date = DateValue::from_value(value)?;
+                let shifted = add_business_days(date.date(), *days, holidays)?;
+                Ok(date.with_date(shifted)?.to_value())
+            }
+            DateOperation::Diff { to, unit, holidays } => {
+                let from = DateValue::from_value(value)?;
+                let elapsed = || to.to_datetime() - from.to_datetime();
+                let difference = match unit {
+                    DateUnit::BusinessDays => business_days_between(from.date(), to.date(), holidays),
+                    DateUnit::Days => match (from, *to) {
+                        (DateValue::Date(from), DateValue::Date(to)) => (to - from).num_days(),
+                        _ => elapsed().num_days(),
+                    },
+                    DateUnit::Hours => elapsed().num_hours(),
+                    DateUnit::Minutes => elapsed().num_minutes(),
+                    DateUnit::Seconds => elapsed().num_seconds(),
+                    DateUnit::Milliseconds => elapsed().num_milliseconds(),
+                };
+                Ok(Value::from(difference))
+            }
+        }
+    }
+}
+
+fn parse(value: &Value, format: &str, timezone: Option<Tz>) -> Result<DateValue, EnrichmentError> {
+    if format == "epochSeconds" || format == "epochMillis" {
+        let timestamp = match value {
+            Value::Number(n) => n.as_i64(),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| EnrichmentError::TransformError("Epoch timestamp is not an integer".into()))?;
+        let datetime = if format == "epochSeconds" {
+            DateTime::<Utc>::from_timestamp(timestamp, 0)
+        } else {
+            DateTime::<Utc>::from_timestamp_millis(timestamp)
+        }
+        .ok_or_else(|| EnrichmentError::TransformError(format!("Timestamp {} is out of range", timestamp)))?;
+        return Ok(DateValue::DateTime(match timezone {
+            Some(tz) => datetime.with_timezone(&tz).fixed_offset(),
+            None => datetime.fixed_offset(),
+        }));
+    }
+
+    let s = value.as_str()
+        .ok_or_else(|| EnrichmentError::TransformError("Value is not a string".into()))?;
+    let invalid = |e: chrono::ParseError| EnrichmentError::TransformError(format!("'{}' does not match {}: {}", s, format, e));
+
+    if format == "rfc3339" {
+        return DateTime::parse_from_rfc3339(s).map(DateValue::DateTime).map_err(invalid);
+    }
+    if let Ok(datetime) = DateTime::parse_from_str(s, format) {
+        return Ok(DateValue::DateTime(datetime));
+    }
+    if let Ok(naive) = NaiveDateTime::parse_from_str(s, format) {
+        return localize(naive, timezone.unwrap_or(Tz::UTC)).map(DateValue::DateTime);
+    }
+    let date = NaiveDate::parse_from_str(s, format).map_err(invalid)?;
+    match timezone {
+        Some(tz) => localize(date.and_time(Default::default()), tz).map(DateValue::DateTime),
+        None => Ok(DateValue::Date(date)),
+    }
+}
+
+/// Interpret a wall-clock time in a zone, taking the earlier instant when a DST change makes it ambiguous
+fn localize(naive: NaiveDateTime, tz: Tz) -> Result<DateTime<FixedOffset>, EnrichmentError> {
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .map(|datetime| datetime.fixed_offset())
+        .ok_or_else(|| EnrichmentError::TransformError(format!("{} does not exist in {}", naive, tz)))
+}
+
+fn is_business_day(date: NaiveDate, holidays: &[NaiveDate]) -> bool {
+    !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !holidays.contains(&date)
+}
+
+fn add_business_days(mut date: NaiveDate, days: i64, holidays: &[NaiveDate]) -> Result<NaiveDate, EnrichmentError> {
+    let step = if days < 0 { Duration::days(-1) } else { Duration::days(1) };
+    let mut remaining = days.unsigned_abs();
+    while remaining > 0 {
+        date = date.checked_add_signed(step)
+            .ok_or_else(|| EnrichmentError::TransformError("Date out of range".into()))?;
+        if is_business_day(date, holidays) {
+            remaining -= 1;
+        }
+    }
+    Ok(date)
+}
+
+/// Count business days after `from` up to and including `to`, negative when `to` is earlier
+fn business_days_between(from: NaiveDate, to: NaiveDate, holidays: &[NaiveDate]) -> i64 {
+    let (start, end, sign) = if to >= from { (from, to, 1) } else { (to, from, -1) };
+    let count = start.iter_days()
+        .skip(1)
+        .take_while(|date| *date <= end)
+        .filter(|date| is_business_day(*date, holidays))
+        .count() as i64;
+    sign * count
+}
@@ -0,0 +1,136 @@
+use super::Transformer;
+use crate::error::EnrichmentError;
+use crate::jsonpath::JsonPathExtractor;
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine as _;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Characters left as-is by `urlEncode`: the RFC 3986 unreserved set
+const UNRESERVED: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_').remove(b'.').remove(b'~');
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Base64Params {
+    /// Use the URL-safe alphabet with `-` and `_`
+    #[serde(default)]
+    pub url_safe: bool,
+    /// Emit `=` padding when encoding; decoding accepts input with or without it
+    #[serde(default = "default_padding")]
+    pub padding: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct HexParams {
+    /// Emit uppercase digits when encoding
+    #[serde(default)]
+    pub uppercase: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ParseJsonParams {
+    /// JSONPath selecting part of the parsed document, defaulting to all of it
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct StringifyJsonParams {
+    /// Indent the output over multiple lines
+    #[serde(default)]
+    pub pretty: bool,
+}
+
+fn default_padding() -> bool {
+    true
+}
+
+pub enum EncodingOperation {
+    Base64Encode { url_safe: bool, padding: bool },
+    Base64Decode { url_safe: bool },
+    HexEncode { uppercase: bool },
+    HexDecode,
+    UrlEncode,
+    UrlDecode,
+    ParseJson(Option<String>),
+    StringifyJson { pretty: bool },
+}
+
+/// Encoding transforms; decoded bytes must be valid UTF-8 since results are JSON strings
+pub struct EncodingTransformer {
+    operation: EncodingOperation,
+}
+
+impl EncodingTransformer {
+    pub fn new(operation: EncodingOperation) -> Self {
+        Self { operation }
+    }
+}
+
+impl Transformer for EncodingTransformer {
+    fn transform(&self, value: &Value) -> Result<Value, EnrichmentError> {
+        // Every operation except stringifyJson needs text, taking numbers and booleans as written
+        let text = match value {
+            Value::String(s) => Ok(s.clone()),
+            Value::Number(_) | Value::Bool(_) => Ok(value.to_string()),
+            _ => Err(EnrichmentError::TransformError("Value is not a string".into())),
+        };
+
+        match &self.operation {
+            EncodingOperation::Base64Encode { url_safe, padding } => {
+                Ok(Value::String(base64_engine(*url_safe, *padding).encode(text?.as_bytes())))
+            }
+            EncodingOperation::Base64Decode { url_safe } => {
+                let bytes = base64_engine(*url_safe, false)
+                    .decode(text?.trim())
+                    .map_err(|e| EnrichmentError::TransformError(format!("Invalid base64: {}", e)))?;
+                utf8(bytes)
+            }
+            EncodingOperation::HexEncode { uppercase } => {
+                let text = text?;
+                Ok(Value::String(if *uppercase { hex::encode_upper(text.as_bytes()) } else { hex::encode(text.as_bytes()) }))
+            }
+            EncodingOperation::HexDecode => {
+                let bytes = hex::decode(text?.trim())
+                    .map_err(|e| EnrichmentError::TransformError(format!("Invalid hex: {}", e)))?;
+                utf8(bytes)
+            }
+            EncodingOperation::UrlEncode => Ok(Value::String(utf8_percent_encode(&text?, UNRESERVED).to_string())),
+            EncodingOperation::UrlDecode => utf8(percent_decode_str(&text?).collect()),
+            EncodingOperation::ParseJson(path) => {
+                let parsed: Value = serde_json::from_str(&text?)
+                    .map_err(|e| EnrichmentError::TransformError(format!("Invalid embedded JSON: {}", e)))?;
+                match path {
+                    Some(path) => JsonPathExtractor::select(&parsed, path),
+                    None => Ok(parsed),
+                }
+            }
+            EncodingOperation::StringifyJson { pretty } => {
+                let text = if *pretty { serde_json::to_string_pretty(value)? } else { serde_json::to_string(value)? };
+                Ok(Value::String(text))
+            }
+        }
+    }
+}
+
+fn base64_engine(url_safe: bool, padding: bool) -> GeneralPurpose {
+    let alphabet = if url_safe { &alphabet::URL_SAFE } else { &alphabet::STANDARD };
+    let config = GeneralPurposeConfig::new()
+        .with_encode_padding(padding)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent);
+    GeneralPurpose::new(alphabet, config)
+}
+
+fn utf8(bytes: Vec<u8>) -> Result<Value, EnrichmentError> {
+    String::from_utf8(bytes)
+        .map(Value::String)
+        .map_err(|_| EnrichmentError::TransformError("Decoded bytes are not valid UTF-8".into()))
+}
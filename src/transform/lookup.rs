@@ -0,0 +1,78 @@
+use super::Transformer;
+use crate::error::EnrichmentError;
+use crate::lookup::LookupTable;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Table consulted by a `lookup` transform
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum TableSource {
+    /// Name of a table registered on the engine, which may reference variables as `${name}`
+    Named(String),
+    /// Entries given directly in the spec
+    Inline(LookupTable),
+}
+
+/// What a `lookup` produces for a value missing from its table
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum OnMissing {
+    /// Fail with a transform error
+    #[default]
+    Error,
+    /// Keep the input value unchanged
+    Passthrough,
+    /// Produce `null`
+    Null,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct LookupParams {
+    pub table: TableSource,
+    /// Value used for missing keys, taking precedence over `onMissing`
+    pub default: Option<Value>,
+    #[serde(default)]
+    pub on_missing: OnMissing,
+}
+
+/// Maps scalar values through a table, matching numbers and booleans by their string form
+pub struct LookupTransformer<'a> {
+    table: &'a LookupTable,
+    default: Option<&'a Value>,
+    on_missing: OnMissing,
+}
+
+impl<'a> LookupTransformer<'a> {
+    pub fn new(table: &'a LookupTable, default: Option<&'a Value>, on_missing: OnMissing) -> Self {
+        Self { table, default, on_missing }
+    }
+}
+
+impl Transformer for LookupTransformer<'_> {
+    fn transform(&self, value: &Value) -> Result<Value, EnrichmentError> {
+        let key = match value {
+            Value::String(s) => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            Value::Bool(b) => Some(b.to_string()),
+            Value::Null => None,
+            _ => return Err(EnrichmentError::TransformError(format!("Cannot look up {}", value))),
+        };
+
+        if let Some(found) = key.as_deref().and_then(|key| self.table.get(key)) {
+            return Ok(found.clone());
+        }
+        if let Some(default) = self.default {
+            return Ok(default.clone());
+        }
+        match self.on_missing {
+            OnMissing::Error => Err(EnrichmentError::TransformError(format!("No lookup entry for {}", value))),
+            OnMissing::Passthrough => Ok(value.clone()),
+            OnMissing::Null => Ok(Value::Null),
+        }
+    }
+}
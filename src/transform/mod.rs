@@ -1,13 +1,325 @@
 mod string;
 mod array;
 mod template;
+mod numeric;
+mod money;
+mod date;
+mod pattern;
+mod coerce;
+mod lookup;
+mod object;
+mod pii;
+mod encoding;
+mod payment;
 
-pub use string::StringTransformer;
-pub use array::ArrayTransformer;
-pub use template::TemplateTransformer;
+pub use string::{
+    CaseStyle, NormalizationForm, NormalizeParams, PadParams, ReplaceParams, StringOperation, StringTransformer,
+    SubstringParams, TrimParams, TruncateParams,
+};
+pub use array::{
+    ArrayOperation, ArrayTransformer, AtParams, ConcatParams, ConcatTransformer, FilterParams, FlattenParams, MapParams,
+    SliceParams, SortParams, SplitParams,
+};
+pub use template::{Template, TemplateParams, TemplateTransformer};
+pub use numeric::{
+    ArithmeticParams, NumericOperation, NumericTransformer, Operand, RoundParams, RoundingMode, ScaleParams,
+    ToIntegerParams,
+};
+pub use money::{
+    CurrencyParam, FormatMoneyParams, MajorUnitsParams, MinorUnitsParams, MoneyOperation, MoneyTransformer,
+    ParseMoneyParams, SumMoneyParams,
+};
+pub use date::{
+    AddBusinessDaysParams, AddDaysParams, ConvertTimezoneParams, DateDiffParams, DateOperation, DateTransformer,
+    DateUnit, DateValue, FormatDateParams, ParseDateParams,
+};
+pub use pattern::{
+    CaptureGroup, Pattern, RegexCapturesParams, RegexExtractParams, RegexMatchParams, RegexOperation,
+    RegexReplaceParams, RegexTransformer,
+};
+pub use coerce::{CoerceOperation, CoerceTransformer, ToBooleanParams, ToNullParams};
+pub use lookup::{LookupParams, LookupTransformer, OnMissing, TableSource};
+pub use object::{KeysParams, MergeParams, ObjectOperation, ObjectTransformer};
+pub use pii::{HashParams, MaskParams, MaskPreset, PiiOperation, PiiTransformer, RedactParams, TokenizeParams};
+pub use encoding::{
+    Base64Params, EncodingOperation, EncodingTransformer, HexParams, ParseJsonParams, StringifyJsonParams,
+};
+pub use payment::{
+    BicParams, IbanComponent, IbanComponentsParams, IbanFormat, IbanParams, PaymentOperation, PaymentTransformer,
+};
+pub(crate) use date::format_date;
+pub(crate) use coerce::coercions;
+pub(crate) use object::merge as merge_objects;
 
+use crate::currency;
+use crate::engine::Scope;
 use crate::error::EnrichmentError;
+use crate::types::Transform;
+use rust_decimal::Decimal;
 
 pub trait Transformer {
     fn transform(&self, value: &serde_json::Value) -> Result<serde_json::Value, EnrichmentError>;
-}
\ No newline at end of file
+}
+
+/// Apply a transform from a mapping rule, resolving `${var}` references in its string params
+pub(crate) fn apply(transform: &Transform, value: &serde_json::Value, scope: &Scope) -> Result<serde_json::Value, EnrichmentError> {
+    let param = |raw: &str| -> Result<String, EnrichmentError> {
+        Ok(scope.interpolate(raw)?.into_owned())
+    };
+
+    match transform {
+        Transform::ToString {} => StringTransformer::new(StringOperation::ToString).transform(value),
+        Transform::ToUpperCase {} => StringTransformer::new(StringOperation::ToUpperCase).transform(value),
+        Transform::ToLowerCase {} => StringTransformer::new(StringOperation::ToLowerCase).transform(value),
+        Transform::Split(params) => ArrayTransformer::new(ArrayOperation::Split(param(&params.delimiter)?)).transform(value),
+        Transform::Concat(params) => ConcatTransformer::new(param(&params.delimiter)?).transform(value),
+        Transform::Replace(params) => StringTransformer::new(StringOperation::Replace {
+            from: param(&params.from)?,
+            to: param(&params.to)?,
+        }).transform(value),
+        Transform::Substring(params) => StringTransformer::new(StringOperation::Substring {
+            start: params.start,
+            length: params.length,
+        }).transform(value),
+        Transform::Trim(params) => trim(true, true, params, &param)?.transform(value),
+        Transform::TrimStart(params) => trim(true, false, params, &param)?.transform(value),
+        Transform::TrimEnd(params) => trim(false, true, params, &param)?.transform(value),
+        Transform::PadLeft(params) => StringTransformer::new(StringOperation::PadLeft {
+            length: params.length,
+            fill: param(&params.fill)?,
+        }).transform(value),
+        Transform::PadRight(params) => StringTransformer::new(StringOperation::PadRight {
+            length: params.length,
+            fill: param(&params.fill)?,
+        }).transform(value),
+        Transform::Truncate(params) => StringTransformer::new(StringOperation::Truncate {
+            length: params.length,
+            ellipsis: param(&params.ellipsis)?,
+        }).transform(value),
+        Transform::Normalize(params) => StringTransformer::new(StringOperation::Normalize(params.form)).transform(value),
+        Transform::StripDiacritics {} => StringTransformer::new(StringOperation::StripDiacritics).transform(value),
+        Transform::CollapseWhitespace {} => StringTransformer::new(StringOperation::CollapseWhitespace).transform(value),
+        Transform::TitleCase {} => StringTransformer::new(StringOperation::Case(CaseStyle::Title)).transform(value),
+        Transform::CamelCase {} => StringTransformer::new(StringOperation::Case(CaseStyle::Camel)).transform(value),
+        Transform::SnakeCase {} => StringTransformer::new(StringOperation::Case(CaseStyle::Snake)).transform(value),
+        Transform::KebabCase {} => StringTransformer::new(StringOperation::Case(CaseStyle::Kebab)).transform(value),
+        Transform::Template(params) => {
+            let bindings = params.bindings.iter()
+                .map(|(name, source)| Ok((name.clone(), scope.resolve_source(source)?)))
+                .collect::<Result<_, EnrichmentError>>()?;
+            let vars = params.template.variables().into_iter()
+                .filter_map(|name| scope.var(name).map(|value| (name.to_string(), value.clone())))
+                .collect();
+            TemplateTransformer::new(&params.template, bindings, vars).transform(value)
+        }
+        Transform::ToNumber {} => NumericTransformer::new(NumericOperation::ToNumber).transform(value),
+        Transform::ToInteger(params) => NumericTransformer::new(NumericOperation::ToInteger(params.mode)).transform(value),
+        Transform::Round(params) => NumericTransformer::new(NumericOperation::Round {
+            places: params.places,
+            mode: params.mode,
+        }).transform(value),
+        Transform::Scale(params) => NumericTransformer::new(NumericOperation::Scale(params.exponent)).transform(value),
+        Transform::Add(params) => NumericTransformer::new(NumericOperation::Add(operand(&params.operand, scope)?)).transform(value),
+        Transform::Subtract(params) => NumericTransformer::new(NumericOperation::Subtract(operand(&params.operand, scope)?)).transform(value),
+        Transform::Multiply(params) => NumericTransformer::new(NumericOperation::Multiply(operand(&params.operand, scope)?)).transform(value),
+        Transform::Divide(params) => NumericTransformer::new(NumericOperation::Divide(operand(&params.operand, scope)?)).transform(value),
+        Transform::Abs {} => NumericTransformer::new(NumericOperation::Abs).transform(value),
+        Transform::ParseMoney(params) => {
+            let decimal_separator = param(&params.decimal_separator)?;
+            let group_separator = match &params.group_separator {
+                Some(separator) => param(separator)?,
+                None if decimal_separator == "," => ".".into(),
+                None => ",".into(),
+            };
+            MoneyTransformer::new(MoneyOperation::Parse { decimal_separator, group_separator }).transform(value)
+        }
+        Transform::FormatMoney(params) => {
+            let (code, exponent) = currency(&params.currency, scope)?;
+            MoneyTransformer::new(MoneyOperation::Format {
+                exponent,
+                mode: params.mode,
+                decimal_separator: param(&params.decimal_separator)?,
+                group_separator: param(&params.group_separator)?,
+                code: params.include_code.then_some(code),
+            }).transform(value)
+        }
+        Transform::ToMinorUnits(params) => {
+            let (_, exponent) = currency(&params.currency, scope)?;
+            MoneyTransformer::new(MoneyOperation::ToMinorUnits { exponent, mode: params.mode }).transform(value)
+        }
+        Transform::ToMajorUnits(params) => {
+            let (_, exponent) = currency(&params.currency, scope)?;
+            MoneyTransformer::new(MoneyOperation::ToMajorUnits { exponent }).transform(value)
+        }
+        Transform::SumMoney(params) => {
+            let exponent = match &params.currency {
+                Some(param) => Some(currency(param, scope)?.1),
+                None => None,
+            };
+            MoneyTransformer::new(MoneyOperation::Sum { exponent }).transform(value)
+        }
+        Transform::ParseDate(params) => DateTransformer::new(DateOperation::Parse {
+            format: param(&params.format)?,
+            timezone: timezone(params.timezone.as_deref(), scope)?,
+        }).transform(value),
+        Transform::FormatDate(params) => DateTransformer::new(DateOperation::Format {
+            format: param(&params.format)?,
+            timezone: timezone(params.timezone.as_deref(), scope)?,
+        }).transform(value),
+        Transform::ConvertTimezone(params) => {
+            let tz = date::parse_timezone(&param(&params.timezone)?)?;
+            DateTransformer::new(DateOperation::ConvertTimezone(tz)).transform(value)
+        }
+        Transform::AddDays(params) => DateTransformer::new(DateOperation::AddDays(params.days)).transform(value),
+        Transform::AddBusinessDays(params) => DateTransformer::new(DateOperation::AddBusinessDays {
+            days: params.days,
+            holidays: params.holidays.clone(),
+        }).transform(value),
+        Transform::DateDiff(params) => DateTransformer::new(DateOperation::Diff {
+            to: DateValue::from_value(&scope.resolve_source(&params.to)?)?,
+            unit: params.unit,
+            holidays: params.holidays.clone(),
+        }).transform(value),
+        Transform::RegexMatch(params) => {
+            RegexTransformer::new(RegexOperation::Match(params.pattern.regex())).transform(value)
+        }
+        Transform::RegexExtract(params) => RegexTransformer::new(RegexOperation::Extract {
+            regex: params.pattern.regex(),
+            group: params.group.clone(),
+        }).transform(value),
+        Transform::RegexCaptures(params) => {
+            RegexTransformer::new(RegexOperation::Captures(params.pattern.regex())).transform(value)
+        }
+        Transform::RegexReplace(params) => RegexTransformer::new(RegexOperation::Replace {
+            regex: params.pattern.regex(),
+            replacement: params.replacement.clone(),
+            first: params.first,
+        }).transform(value),
+        Transform::ToBoolean(params) => CoerceTransformer::new(CoerceOperation::ToBoolean {
+            truthy: params.truthy.clone(),
+            falsy: params.falsy.clone(),
+            case_sensitive: params.case_sensitive,
+        }).transform(value),
+        Transform::ToNull(params) => CoerceTransformer::new(CoerceOperation::ToNull(params.values.clone())).transform(value),
+        Transform::Lookup(params) => {
+            let table = match &params.table {
+                TableSource::Named(name) => scope.table(&param(name)?)?,
+                TableSource::Inline(table) => table,
+            };
+            LookupTransformer::new(table, params.default.as_ref(), params.on_missing).transform(value)
+        }
+        Transform::First {} => ArrayTransformer::new(ArrayOperation::First).transform(value),
+        Transform::Last {} => ArrayTransformer::new(ArrayOperation::Last).transform(value),
+        Transform::At(params) => ArrayTransformer::new(ArrayOperation::At(params.index)).transform(value),
+        Transform::Slice(params) => ArrayTransformer::new(ArrayOperation::Slice {
+            start: params.start,
+            end: params.end,
+        }).transform(value),
+        Transform::Filter(params) => ArrayTransformer::new(ArrayOperation::Filter(params.condition.clone())).transform(value),
+        Transform::Map(params) => array::map(&params.transforms, value, scope),
+        Transform::Sort(params) => ArrayTransformer::new(ArrayOperation::Sort {
+            by: params.by.as_deref().map(param).transpose()?,
+            descending: params.descending,
+        }).transform(value),
+        Transform::Unique {} => ArrayTransformer::new(ArrayOperation::Unique).transform(value),
+        Transform::Flatten(params) => ArrayTransformer::new(ArrayOperation::Flatten(params.depth)).transform(value),
+        Transform::Count {} => ArrayTransformer::new(ArrayOperation::Count).transform(value),
+        Transform::Sum {} => ArrayTransformer::new(ArrayOperation::Sum).transform(value),
+        Transform::Pick(params) => ObjectTransformer::new(ObjectOperation::Pick(params.keys.clone())).transform(value),
+        Transform::Omit(params) => ObjectTransformer::new(ObjectOperation::Omit(params.keys.clone())).transform(value),
+        Transform::Merge(params) => ObjectTransformer::new(ObjectOperation::Merge { deep: params.deep }).transform(value),
+        Transform::Entries {} => ObjectTransformer::new(ObjectOperation::Entries).transform(value),
+        Transform::FromEntries {} => ObjectTransformer::new(ObjectOperation::FromEntries).transform(value),
+        Transform::Mask(params) => PiiTransformer::new(PiiOperation::Mask {
+            preset: params.preset,
+            keep_first: params.keep_first,
+            keep_last: params.keep_last,
+            character: params.character,
+        }).transform(value),
+        Transform::Hash(params) => {
+            let key = match &params.key {
+                Some(name) => Some(scope.hash_key(&param(name)?)?),
+                None => None,
+            };
+            PiiTransformer::new(PiiOperation::Hash { key }).transform(value)
+        }
+        Transform::Redact(params) => PiiTransformer::new(PiiOperation::Redact {
+            pattern: params.pattern.as_ref(),
+            replacement: params.replacement.clone(),
+        }).transform(value),
+        Transform::Tokenize(params) => PiiTransformer::new(PiiOperation::Tokenize {
+            vault: scope.token_vault()?,
+            namespace: param(&params.namespace)?,
+        }).transform(value),
+        Transform::Base64Encode(params) => EncodingTransformer::new(EncodingOperation::Base64Encode {
+            url_safe: params.url_safe,
+            padding: params.padding,
+        }).transform(value),
+        Transform::Base64Decode(params) => {
+            EncodingTransformer::new(EncodingOperation::Base64Decode { url_safe: params.url_safe }).transform(value)
+        }
+        Transform::HexEncode(params) => {
+            EncodingTransformer::new(EncodingOperation::HexEncode { uppercase: params.uppercase }).transform(value)
+        }
+        Transform::HexDecode {} => EncodingTransformer::new(EncodingOperation::HexDecode).transform(value),
+        Transform::UrlEncode {} => EncodingTransformer::new(EncodingOperation::UrlEncode).transform(value),
+        Transform::UrlDecode {} => EncodingTransformer::new(EncodingOperation::UrlDecode).transform(value),
+        Transform::ParseJson(params) => {
+            let path = params.path.as_deref().map(param).transpose()?;
+            EncodingTransformer::new(EncodingOperation::ParseJson(path)).transform(value)
+        }
+        Transform::StringifyJson(params) => {
+            EncodingTransformer::new(EncodingOperation::StringifyJson { pretty: params.pretty }).transform(value)
+        }
+        Transform::Iban(params) => PaymentTransformer::new(PaymentOperation::Iban(params.format)).transform(value),
+        Transform::Bic(params) => {
+            PaymentTransformer::new(PaymentOperation::Bic { expand: params.expand }).transform(value)
+        }
+        Transform::CountryCode {} => PaymentTransformer::new(PaymentOperation::CountryCode).transform(value),
+        Transform::CurrencyCode {} => PaymentTransformer::new(PaymentOperation::CurrencyCode).transform(value),
+        Transform::IbanComponents(params) => {
+            PaymentTransformer::new(PaymentOperation::IbanComponents(params.component)).transform(value)
+        }
+    }
+}
+
+fn trim(
+    start: bool,
+    end: bool,
+    params: &TrimParams,
+    param: &dyn Fn(&str) -> Result<String, EnrichmentError>,
+) -> Result<StringTransformer, EnrichmentError> {
+    let characters = params.characters.as_deref().map(param).transpose()?;
+    Ok(StringTransformer::new(StringOperation::Trim { start, end, characters }))
+}
+
+fn timezone(name: Option<&str>, scope: &Scope) -> Result<Option<chrono_tz::Tz>, EnrichmentError> {
+    name.map(|name| date::parse_timezone(&scope.interpolate(name)?)).transpose()
+}
+
+/// Resolve a currency param to its code and ISO 4217 minor unit digits
+fn currency(param: &CurrencyParam, scope: &Scope) -> Result<(String, u32), EnrichmentError> {
+    let code = match param {
+        CurrencyParam::Code(code) => scope.interpolate(code)?.into_owned(),
+        CurrencyParam::Source { source } => match scope.select(source)? {
+            serde_json::Value::String(code) => code,
+            _ => return Err(EnrichmentError::TransformError(format!("Currency at {} is not a string", source))),
+        },
+    };
+    let exponent = currency::minor_units(&code)
+        .ok_or_else(|| EnrichmentError::ValidationError(format!("Unknown ISO 4217 currency code: {}", code)))?;
+    Ok((code, exponent))
+}
+
+/// Resolve an arithmetic operand to a number, reading other sources and variables as needed
+fn operand(operand: &Operand, scope: &Scope) -> Result<Decimal, EnrichmentError> {
+    match operand {
+        Operand::Number(n) => numeric::number_to_decimal(n),
+        Operand::Source { source } => match scope.select(source)? {
+            serde_json::Value::Number(n) => numeric::number_to_decimal(&n),
+            serde_json::Value::String(s) => numeric::parse_decimal(s.trim()),
+            _ => Err(EnrichmentError::TransformError(format!("Operand at {} is not a number", source))),
+        },
+        Operand::Text(text) => numeric::parse_decimal(scope.interpolate(text)?.trim()),
+    }
+}
@@ -0,0 +1,225 @@
+use super::numeric::{number_to_decimal, parse_decimal, RoundingMode};
+use super::Transformer;
+use crate::error::EnrichmentError;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// ISO 4217 currency of an amount
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum CurrencyParam {
+    /// A currency code, which may reference variables as `${name}`
+    Code(String),
+    /// A currency code read from another path in the input
+    Source { source: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ParseMoneyParams {
+    /// Separator between the major and minor units
+    #[serde(default = "default_decimal_separator")]
+    pub decimal_separator: String,
+    /// Thousands separator to strip, defaulting to `,` (or `.` when the decimal separator is `,`)
+    pub group_separator: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FormatMoneyParams {
+    pub currency: CurrencyParam,
+    /// Rounding applied when the amount has more decimals than the currency allows
+    #[serde(default)]
+    pub mode: RoundingMode,
+    #[serde(default = "default_decimal_separator")]
+    pub decimal_separator: String,
+    /// Thousands separator inserted into the major units
+    #[serde(default)]
+    pub group_separator: String,
+    /// Prefix the result with the currency code
+    #[serde(default)]
+    pub include_code: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MinorUnitsParams {
+    pub currency: CurrencyParam,
+    /// Rounding applied to amounts finer than the minor unit; without it they are rejected
+    pub mode: Option<RoundingMode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MajorUnitsParams {
+    pub currency: CurrencyParam,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SumMoneyParams {
+    /// Currency whose minor unit fixes the number of decimals in the result
+    pub currency: Option<CurrencyParam>,
+}
+
+fn default_decimal_separator() -> String {
+    ".".into()
+}
+
+pub enum MoneyOperation {
+    Parse { decimal_separator: String, group_separator: String },
+    Format { exponent: u32, mode: RoundingMode, decimal_separator: String, group_separator: String, code: Option<String> },
+    ToMinorUnits { exponent: u32, mode: Option<RoundingMode> },
+    ToMajorUnits { exponent: u32 },
+    Sum { exponent: Option<u32> },
+}
+
+/// Money transforms computing in decimal arithmetic and emitting major-unit amounts as strings,
+/// so values never pass through binary floating point
+pub struct MoneyTransformer {
+    operation: MoneyOperation,
+}
+
+impl MoneyTransformer {
+    pub fn new(operation: MoneyOperation) -> Self {
+        Self { operation }
+    }
+}
+
+impl Transformer for MoneyTransformer {
+    fn transform(&self, value: &Value) -> Result<Value, EnrichmentError> {
+        match &self.operation {
+            MoneyOperation::Parse { decimal_separator, group_separator } => {
+                let text = value.as_str()
+                    .ok_or_else(|| EnrichmentError::TransformError("Value is not a string".into()))?;
+                let normalized = normalize_amount(text, decimal_separator, group_separator)?;
+                Ok(Value::String(parse_decimal(&normalized)?.normalize().to_string()))
+            }
+            MoneyOperation::Format { exponent, mode, decimal_separator, group_separator, code } => {
+                let amount = rescale(amount(value)?, *exponent, Some(*mode))?;
+                let formatted = format_amount(amount, decimal_separator, group_separator);
+                Ok(Value::String(match code {
+                    Some(code) => format!("{} {}", code, formatted),
+                    None => formatted,
+                }))
+            }
+            MoneyOperation::ToMinorUnits { exponent, mode } => {
+                let amount = rescale(amount(value)?, *exponent, *mode)?;
+                let minor = Decimal::from_i128_with_scale(amount.mantissa(), 0);
+                i64::try_from(minor)
+                    .map(Value::from)
+                    .map_err(|_| EnrichmentError::TransformError(format!("Minor units of {} overflow", amount)))
+            }
+            MoneyOperation::ToMajorUnits { exponent } => {
+                let minor = amount(value)?;
+                if !minor.is_integer() {
+                    return Err(EnrichmentError::TransformError(format!("Minor units {} are not an integer", minor)));
+                }
+                // Drop any fractional scale such as `1500.0` so set_scale shifts the integer value itself
+                let mut major = Decimal::from_i128_with_scale(minor.normalize().mantissa(), 0);
+                major.set_scale(*exponent)
+                    .map_err(|_| EnrichmentError::TransformError(format!("Cannot scale {} by {} digits", minor, exponent)))?;
+                Ok(Value::String(major.to_string()))
+            }
+            MoneyOperation::Sum { exponent } => {
+                let amounts = value.as_array()
+                    .ok_or_else(|| EnrichmentError::TransformError("Value is not an array".into()))?;
+                let total = amounts.iter().try_fold(Decimal::ZERO, |total, value| {
+                    total.checked_add(amount(value)?)
+                        .ok_or_else(|| EnrichmentError::TransformError("Numeric overflow in sumMoney".into()))
+                })?;
+                let total = match exponent {
+                    Some(exponent) => rescale(total, *exponent, None)?,
+                    None => total.normalize(),
+                };
+                Ok(Value::String(total.to_string()))
+            }
+        }
+    }
+}
+
+/// Rewrite a localized amount with `.` as decimal separator and no grouping, rejecting separators
+/// that do not split the major units into groups of three rather than guessing what was meant
+fn normalize_amount(text: &str, decimal_separator: &str, group_separator: &str) -> Result<String, EnrichmentError> {
+    let invalid = |reason: &str| EnrichmentError::TransformError(format!("Invalid amount '{}': {}", text, reason));
+    if decimal_separator.is_empty() || decimal_separator == group_separator {
+        return Err(invalid("the decimal separator must be non-empty and differ from the group separator"));
+    }
+
+    let text = text.trim();
+    let (sign, unsigned) = text.split_at(usize::from(text.starts_with(['-', '+'])));
+    let mut parts = unsigned.split(decimal_separator);
+    let integer = parts.next().unwrap_or_default();
+    let fraction = parts.next();
+    if parts.next().is_some() {
+        return Err(invalid("more than one decimal separator"));
+    }
+
+    let mut groups = vec![integer];
+    if !group_separator.is_empty() {
+        if fraction.is_some_and(|f| f.contains(group_separator)) {
+            return Err(invalid("group separator after the decimal separator"));
+        }
+        groups = integer.split(group_separator).collect();
+        let (first, rest) = groups.split_first().expect("split yields at least one group");
+        if !rest.is_empty() && (!(1..=3).contains(&first.len()) || rest.iter().any(|group| group.len() != 3)) {
+            return Err(invalid("digit groups after the first must have exactly three digits"));
+        }
+    }
+    Ok(format!("{}{}{}", sign, groups.concat(), fraction.map(|f| format!(".{}", f)).unwrap_or_default()))
+}
+
+/// Read an amount from a JSON number or numeric string without going through `f64`
+fn amount(value: &Value) -> Result<Decimal, EnrichmentError> {
+    match value {
+        Value::Number(n) => number_to_decimal(n),
+        Value::String(s) => parse_decimal(s.trim()),
+        _ => Err(EnrichmentError::TransformError("Amount is not a number".into())),
+    }
+}
+
+/// Give the amount exactly `exponent` decimals, rounding only when a mode is given
+fn rescale(amount: Decimal, exponent: u32, mode: Option<RoundingMode>) -> Result<Decimal, EnrichmentError> {
+    let mut result = match mode {
+        Some(mode) => amount.round_dp_with_strategy(exponent, mode.into()),
+        None if amount.normalize().scale() <= exponent => amount,
+        None => {
+            return Err(EnrichmentError::TransformError(format!(
+                "Amount {} has more than {} decimal places", amount, exponent
+            )))
+        }
+    };
+    result.rescale(exponent);
+    Ok(result)
+}
+
+fn format_amount(amount: Decimal, decimal_separator: &str, group_separator: &str) -> String {
+    let text = amount.abs().to_string();
+    let (major, minor) = match text.split_once('.') {
+        Some((major, minor)) => (major, Some(minor)),
+        None => (text.as_str(), None),
+    };
+
+    let mut result = String::new();
+    if amount.is_sign_negative() && !amount.is_zero() {
+        result.push('-');
+    }
+    for (i, digit) in major.chars().enumerate() {
+        if i > 0 && (major.len() - i) % 3 == 0 {
+            result.push_str(group_separator);
+        }
+        result.push(digit);
+    }
+    if let Some(minor) = minor {
+        result.push_str(decimal_separator);
+        result.push_str(minor);
+    }
+    result
+}
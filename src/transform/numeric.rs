@@ -0,0 +1,179 @@
+use super::Transformer;
+use crate::error::EnrichmentError;
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum RoundingMode {
+    /// Round half to the nearest even digit (banker's rounding)
+    #[default]
+    #[serde(alias = "half-even", alias = "half_even")]
+    HalfEven,
+    /// Round half away from zero
+    #[serde(alias = "half-up", alias = "half_up")]
+    HalfUp,
+    /// Round towards negative infinity
+    Floor,
+    /// Round towards positive infinity
+    Ceil,
+}
+
+impl From<RoundingMode> for RoundingStrategy {
+    fn from(mode: RoundingMode) -> Self {
+        match mode {
+            RoundingMode::HalfEven => RoundingStrategy::MidpointNearestEven,
+            RoundingMode::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+            RoundingMode::Floor => RoundingStrategy::ToNegativeInfinity,
+            RoundingMode::Ceil => RoundingStrategy::ToPositiveInfinity,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ToIntegerParams {
+    /// Rounding applied to fractional values; without it they are rejected
+    pub mode: Option<RoundingMode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct RoundParams {
+    /// Number of decimal places to keep
+    #[serde(default)]
+    pub places: u32,
+    #[serde(default)]
+    pub mode: RoundingMode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ScaleParams {
+    /// Power of ten to multiply by; negative values divide
+    pub exponent: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ArithmeticParams {
+    pub operand: Operand,
+}
+
+/// Right-hand side of an arithmetic transform
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum Operand {
+    /// A constant number
+    Number(Number),
+    /// A number read from another path in the input
+    Source { source: String },
+    /// A numeric string, which may reference variables as `${name}`
+    Text(String),
+}
+
+pub enum NumericOperation {
+    ToNumber,
+    ToInteger(Option<RoundingMode>),
+    Round { places: u32, mode: RoundingMode },
+    Scale(i32),
+    Add(Decimal),
+    Subtract(Decimal),
+    Multiply(Decimal),
+    Divide(Decimal),
+    Abs,
+}
+
+pub struct NumericTransformer {
+    operation: NumericOperation,
+}
+
+impl NumericTransformer {
+    pub fn new(operation: NumericOperation) -> Self {
+        Self { operation }
+    }
+}
+
+impl Transformer for NumericTransformer {
+    fn transform(&self, value: &Value) -> Result<Value, EnrichmentError> {
+        let number = match value {
+            Value::Number(n) => number_to_decimal(n)?,
+            Value::String(s) => parse_decimal(s.trim())?,
+            _ => return Err(EnrichmentError::TransformError("Value is not a number".into())),
+        };
+
+        let result = match &self.operation {
+            NumericOperation::ToNumber => number,
+            NumericOperation::ToInteger(mode) => match mode {
+                Some(mode) => number.round_dp_with_strategy(0, (*mode).into()),
+                None if number.is_integer() => number,
+                None => return Err(EnrichmentError::TransformError(format!("{} is not an integer", number))),
+            },
+            NumericOperation::Round { places, mode } => number.round_dp_with_strategy(*places, (*mode).into()),
+            NumericOperation::Scale(exponent) => scale(number, *exponent)?,
+            NumericOperation::Add(operand) => number.checked_add(*operand).ok_or_else(|| overflow("add"))?,
+            NumericOperation::Subtract(operand) => number.checked_sub(*operand).ok_or_else(|| overflow("subtract"))?,
+            NumericOperation::Multiply(operand) => number.checked_mul(*operand).ok_or_else(|| overflow("multiply"))?,
+            NumericOperation::Divide(operand) => {
+                if operand.is_zero() {
+                    return Err(EnrichmentError::TransformError("Division by zero".into()));
+                }
+                number.checked_div(*operand).ok_or_else(|| overflow("divide"))?
+            }
+            NumericOperation::Abs => number.abs(),
+        };
+
+        decimal_to_value(result)
+    }
+}
+
+fn overflow(operation: &str) -> EnrichmentError {
+    EnrichmentError::TransformError(format!("Numeric overflow in {}", operation))
+}
+
+fn scale(number: Decimal, exponent: i32) -> Result<Decimal, EnrichmentError> {
+    let factor = (0..exponent.unsigned_abs())
+        .try_fold(Decimal::ONE, |factor, _| factor.checked_mul(Decimal::TEN))
+        .ok_or_else(|| overflow("scale"))?;
+    if exponent >= 0 {
+        number.checked_mul(factor).ok_or_else(|| overflow("scale"))
+    } else {
+        number.checked_div(factor).ok_or_else(|| overflow("scale"))
+    }
+}
+
+/// Parse a numeric string, accepting plain and scientific notation
+pub(crate) fn parse_decimal(s: &str) -> Result<Decimal, EnrichmentError> {
+    Decimal::from_str(s)
+        .or_else(|_| Decimal::from_scientific(s))
+        .map_err(|_| EnrichmentError::TransformError(format!("'{}' is not a number", s)))
+}
+
+pub(crate) fn number_to_decimal(number: &Number) -> Result<Decimal, EnrichmentError> {
+    let text = number.to_string();
+    Decimal::from_str(&text)
+        .or_else(|_| Decimal::from_scientific(&text))
+        .map_err(|_| EnrichmentError::TransformError(format!("Number {} is out of range", text)))
+}
+
+/// Convert back to a JSON number, as an integer whenever the value has no fractional part
+pub(crate) fn decimal_to_value(number: Decimal) -> Result<Value, EnrichmentError> {
+    let number = number.normalize();
+    if number.is_integer() {
+        return i64::try_from(number)
+            .map(Value::from)
+            .or_else(|_| u64::try_from(number).map(Value::from))
+            .map_err(|_| EnrichmentError::TransformError(format!("Integer {} overflows a 64-bit JSON number", number)));
+    }
+    Number::from_str(&number.to_string())
+        .map(Value::Number)
+        .map_err(|_| EnrichmentError::TransformError(format!("{} cannot be represented as a JSON number", number)))
+}
@@ -0,0 +1,126 @@
+use super::Transformer;
+use crate::error::EnrichmentError;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct KeysParams {
+    /// Top-level keys of the object
+    pub keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct MergeParams {
+    /// Merge nested objects key by key instead of replacing them
+    #[serde(default)]
+    pub deep: bool,
+}
+
+pub enum ObjectOperation {
+    Pick(Vec<String>),
+    Omit(Vec<String>),
+    Merge { deep: bool },
+    Entries,
+    FromEntries,
+}
+
+/// Object transforms; `merge` and `fromEntries` take arrays, the others take objects
+pub struct ObjectTransformer {
+    operation: ObjectOperation,
+}
+
+impl ObjectTransformer {
+    pub fn new(operation: ObjectOperation) -> Self {
+        Self { operation }
+    }
+}
+
+impl Transformer for ObjectTransformer {
+    fn transform(&self, value: &Value) -> Result<Value, EnrichmentError> {
+        match &self.operation {
+            ObjectOperation::Pick(keys) => {
+                let object = as_object(value)?;
+                let picked = keys.iter()
+                    .filter_map(|key| object.get(key).map(|value| (key.clone(), value.clone())))
+                    .collect();
+                Ok(Value::Object(picked))
+            }
+            ObjectOperation::Omit(keys) => {
+                let mut object = as_object(value)?.clone();
+                for key in keys {
+                    object.remove(key);
+                }
+                Ok(Value::Object(object))
+            }
+            ObjectOperation::Merge { deep } => {
+                let mut merged = Map::new();
+                for item in as_array(value)? {
+                    match item {
+                        Value::Object(object) => merge(&mut merged, object, *deep),
+                        Value::Null => {}
+                        _ => return Err(EnrichmentError::TransformError(format!("Cannot merge {}", item))),
+                    }
+                }
+                Ok(Value::Object(merged))
+            }
+            ObjectOperation::Entries => {
+                let entries = as_object(value)?.iter()
+                    .map(|(key, value)| {
+                        let mut entry = Map::new();
+                        entry.insert("key".into(), Value::String(key.clone()));
+                        entry.insert("value".into(), value.clone());
+                        Value::Object(entry)
+                    })
+                    .collect();
+                Ok(Value::Array(entries))
+            }
+            ObjectOperation::FromEntries => {
+                let mut object = Map::new();
+                for entry in as_array(value)? {
+                    let (key, value) = match entry {
+                        Value::Object(entry) => (entry.get("key"), entry.get("value")),
+                        Value::Array(pair) if pair.len() == 2 => (pair.first(), pair.get(1)),
+                        _ => (None, None),
+                    };
+                    let key = match key {
+                        Some(Value::String(key)) => key.clone(),
+                        Some(Value::Number(_) | Value::Bool(_)) => key.map(Value::to_string).unwrap_or_default(),
+                        _ => {
+                            return Err(EnrichmentError::TransformError(format!(
+                                "Entry {} needs a scalar key and a value", entry
+                            )))
+                        }
+                    };
+                    object.insert(key, value.cloned().unwrap_or(Value::Null));
+                }
+                Ok(Value::Object(object))
+            }
+        }
+    }
+}
+
+fn as_object(value: &Value) -> Result<&Map<String, Value>, EnrichmentError> {
+    value.as_object()
+        .ok_or_else(|| EnrichmentError::TransformError("Value is not an object".into()))
+}
+
+fn as_array(value: &Value) -> Result<&Vec<Value>, EnrichmentError> {
+    value.as_array()
+        .ok_or_else(|| EnrichmentError::TransformError("Value is not an array".into()))
+}
+
+/// Copy `source` into `target`, later keys winning; nested objects are combined when `deep` is set
+pub(crate) fn merge(target: &mut Map<String, Value>, source: &Map<String, Value>, deep: bool) {
+    for (key, value) in source {
+        match (target.get_mut(key), value) {
+            (Some(Value::Object(existing)), Value::Object(incoming)) if deep => merge(existing, incoming, deep),
+            _ => {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use super::Transformer;
+use crate::error::EnrichmentError;
+use regex::Regex;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+/// A regular expression compiled when the spec is loaded, so invalid patterns fail at parse time
+#[derive(Debug, Clone)]
+pub struct Pattern(Regex);
+
+impl Pattern {
+    pub fn new(pattern: &str) -> Result<Self, EnrichmentError> {
+        Regex::new(pattern)
+            .map(Self)
+            .map_err(|e| EnrichmentError::SpecError(format!("Invalid regex pattern '{}': {}", pattern, e)))
+    }
+
+    pub fn regex(&self) -> &Regex {
+        &self.0
+    }
+}
+
+impl Serialize for Pattern {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.0.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let pattern = String::deserialize(deserializer)?;
+        Pattern::new(&pattern).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Pattern {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Pattern".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({ "type": "string", "format": "regex" })
+    }
+}
+
+/// Capture group selected by its index or its name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum CaptureGroup {
+    Index(usize),
+    Name(String),
+}
+
+impl Default for CaptureGroup {
+    fn default() -> Self {
+        CaptureGroup::Index(0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct RegexMatchParams {
+    pub pattern: Pattern,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct RegexExtractParams {
+    pub pattern: Pattern,
+    /// Group to return, defaulting to the whole match
+    #[serde(default)]
+    pub group: CaptureGroup,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct RegexCapturesParams {
+    /// Pattern whose named groups become the keys of the result
+    pub pattern: Pattern,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct RegexReplaceParams {
+    pub pattern: Pattern,
+    /// Replacement text, referring to groups as `$1` or `${name}`
+    #[serde(default)]
+    pub replacement: String,
+    /// Replace only the first match instead of every match
+    #[serde(default)]
+    pub first: bool,
+}
+
+pub enum RegexOperation<'a> {
+    Match(&'a Regex),
+    Extract { regex: &'a Regex, group: CaptureGroup },
+    Captures(&'a Regex),
+    Replace { regex: &'a Regex, replacement: String, first: bool },
+}
+
+/// Regex transforms over string values; extraction yields `null` when the pattern does not match
+pub struct RegexTransformer<'a> {
+    operation: RegexOperation<'a>,
+}
+
+impl<'a> RegexTransformer<'a> {
+    pub fn new(operation: RegexOperation<'a>) -> Self {
+        Self { operation }
+    }
+}
+
+impl Transformer for RegexTransformer<'_> {
+    fn transform(&self, value: &Value) -> Result<Value, EnrichmentError> {
+        let text = value.as_str()
+            .ok_or_else(|| EnrichmentError::TransformError("Value is not a string".into()))?;
+
+        match &self.operation {
+            RegexOperation::Match(regex) => Ok(Value::Bool(regex.is_match(text))),
+            RegexOperation::Extract { regex, group } => {
+                let captures = match regex.captures(text) {
+                    Some(captures) => captures,
+                    None => return Ok(Value::Null),
+                };
+                let matched = match group {
+                    CaptureGroup::Index(index) if *index < regex.captures_len() => captures.get(*index),
+                    CaptureGroup::Name(name) if regex.capture_names().flatten().any(|n| n == name) => captures.name(name),
+                    _ => {
+                        return Err(EnrichmentError::TransformError(format!(
+                            "Pattern '{}' has no capture group {}", regex.as_str(), group_label(group)
+                        )))
+                    }
+                };
+                Ok(matched.map_or(Value::Null, |m| Value::String(m.as_str().into())))
+            }
+            RegexOperation::Captures(regex) => {
+                let captures = match regex.captures(text) {
+                    Some(captures) => captures,
+                    None => return Ok(Value::Null),
+                };
+                let groups: Map<String, Value> = regex.capture_names()
+                    .flatten()
+                    .map(|name| {
+                        let value = captures.name(name).map_or(Value::Null, |m| Value::String(m.as_str().into()));
+                        (name.to_string(), value)
+                    })
+                    .collect();
+                Ok(Value::Object(groups))
+            }
+            RegexOperation::Replace { regex, replacement, first } => {
+                let replaced = if *first {
+                    regex.replace(text, replacement.as_str())
+                } else {
+                    regex.replace_all(text, replacement.as_str())
+                };
+                Ok(Value::String(replaced.into_owned()))
+            }
+        }
+    }
+}
+
+fn group_label(group: &CaptureGroup) -> String {
+    match group {
+        CaptureGroup::Index(index) => index.to_string(),
+        CaptureGroup::Name(name) => format!("'{}'", name),
+    }
+}
@@ -0,0 +1,254 @@
+use super::Transformer;
+use crate::country;
+use crate::currency;
+use crate::error::EnrichmentError;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Country code, IBAN length, and the BBAN byte ranges of the bank and optional branch identifiers
+type IbanCountry = (&'static str, usize, (usize, usize), Option<(usize, usize)>);
+
+/// IBAN formats per country from the SWIFT IBAN registry, sorted by code
+const IBAN_COUNTRIES: &[IbanCountry] = &[
+    ("AD", 24, (0, 4), Some((4, 8))), ("AE", 23, (0, 3), None), ("AL", 28, (0, 3), Some((3, 7))),
+    ("AT", 20, (0, 5), None), ("AZ", 28, (0, 4), None), ("BA", 20, (0, 3), Some((3, 6))),
+    ("BE", 16, (0, 3), None), ("BG", 22, (0, 4), Some((4, 8))), ("BH", 22, (0, 4), None),
+    ("BI", 27, (0, 5), Some((5, 10))), ("BR", 29, (0, 8), Some((8, 13))), ("BY", 28, (0, 4), None),
+    ("CH", 21, (0, 5), None), ("CR", 22, (0, 4), None), ("CY", 28, (0, 3), Some((3, 8))),
+    ("CZ", 24, (0, 4), None), ("DE", 22, (0, 8), None), ("DJ", 27, (0, 5), Some((5, 10))),
+    ("DK", 18, (0, 4), None), ("DO", 28, (0, 4), None), ("EE", 20, (0, 2), None),
+    ("EG", 29, (0, 4), Some((4, 8))), ("ES", 24, (0, 4), Some((4, 8))), ("FI", 18, (0, 3), None),
+    ("FK", 18, (0, 2), None), ("FO", 18, (0, 4), None), ("FR", 27, (0, 5), Some((5, 10))),
+    ("GB", 22, (0, 4), Some((4, 10))), ("GE", 22, (0, 2), None), ("GI", 23, (0, 4), None),
+    ("GL", 18, (0, 4), None), ("GR", 27, (0, 3), Some((3, 7))), ("GT", 28, (0, 4), None),
+    ("HN", 28, (0, 4), None), ("HR", 21, (0, 7), None), ("HU", 28, (0, 3), Some((3, 7))),
+    ("IE", 22, (0, 4), Some((4, 10))), ("IL", 23, (0, 3), Some((3, 6))), ("IQ", 23, (0, 4), Some((4, 7))),
+    ("IS", 26, (0, 2), Some((2, 4))), ("IT", 27, (1, 6), Some((6, 11))), ("JO", 30, (0, 4), Some((4, 8))),
+    ("KW", 30, (0, 4), None), ("KZ", 20, (0, 3), None), ("LB", 28, (0, 4), None),
+    ("LC", 32, (0, 4), None), ("LI", 21, (0, 5), None), ("LT", 20, (0, 5), None),
+    ("LU", 20, (0, 3), None), ("LV", 21, (0, 4), None), ("LY", 25, (0, 3), Some((3, 6))),
+    ("MC", 27, (0, 5), Some((5, 10))), ("MD", 24, (0, 2), None), ("ME", 22, (0, 3), None),
+    ("MK", 19, (0, 3), None), ("MN", 20, (0, 4), None), ("MR", 27, (0, 5), Some((5, 10))),
+    ("MT", 31, (0, 4), Some((4, 9))), ("MU", 30, (0, 6), Some((6, 8))), ("NI", 28, (0, 4), None),
+    ("NL", 18, (0, 4), None), ("NO", 15, (0, 4), None), ("OM", 23, (0, 3), None),
+    ("PK", 24, (0, 4), None), ("PL", 28, (0, 8), None), ("PS", 29, (0, 4), None),
+    ("PT", 25, (0, 4), Some((4, 8))), ("QA", 29, (0, 4), None), ("RO", 24, (0, 4), None),
+    ("RS", 22, (0, 3), None), ("RU", 33, (0, 9), Some((9, 14))), ("SA", 24, (0, 2), None),
+    ("SC", 31, (0, 6), Some((6, 8))), ("SD", 18, (0, 2), None), ("SE", 24, (0, 3), None),
+    ("SI", 19, (0, 5), None), ("SK", 24, (0, 4), None), ("SM", 27, (1, 6), Some((6, 11))),
+    ("SO", 23, (0, 4), Some((4, 7))), ("ST", 25, (0, 4), Some((4, 8))), ("SV", 28, (0, 4), None),
+    ("TL", 23, (0, 3), None), ("TN", 24, (0, 2), Some((2, 5))), ("TR", 26, (0, 5), None),
+    ("UA", 29, (0, 6), None), ("VA", 22, (0, 3), None), ("VG", 24, (0, 4), None),
+    ("XK", 20, (0, 2), Some((2, 4))), ("YE", 30, (0, 4), Some((4, 8))),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct IbanParams {
+    #[serde(default)]
+    pub format: IbanFormat,
+}
+
+/// Output form of a normalized IBAN
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum IbanFormat {
+    /// Uppercase without separators, as carried in payment messages
+    #[default]
+    Electronic,
+    /// Groups of four characters separated by spaces, as printed on paper
+    Print,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct BicParams {
+    /// Pad 8-character codes to 11 with the primary office branch `XXX`
+    #[serde(default)]
+    pub expand: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct IbanComponentsParams {
+    /// Return only this component instead of an object with all of them
+    pub component: Option<IbanComponent>,
+}
+
+/// Part of an IBAN; bank and branch codes follow the country's registry entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum IbanComponent {
+    CountryCode,
+    CheckDigits,
+    Bban,
+    BankCode,
+    /// `null` for countries without a branch identifier
+    BranchCode,
+}
+
+pub enum PaymentOperation {
+    Iban(IbanFormat),
+    Bic { expand: bool },
+    CountryCode,
+    CurrencyCode,
+    IbanComponents(Option<IbanComponent>),
+}
+
+/// Normalizes and validates payment identifiers. Separators and case are ignored on input;
+/// invalid identifiers fail with a validation error naming the 1-based position at fault.
+pub struct PaymentTransformer {
+    operation: PaymentOperation,
+}
+
+impl PaymentTransformer {
+    pub fn new(operation: PaymentOperation) -> Self {
+        Self { operation }
+    }
+}
+
+impl Transformer for PaymentTransformer {
+    fn transform(&self, value: &Value) -> Result<Value, EnrichmentError> {
+        let text = match value {
+            Value::String(s) => s,
+            _ => return Err(EnrichmentError::TransformError("Value is not a string".into())),
+        };
+
+        match &self.operation {
+            PaymentOperation::Iban(format) => {
+                let iban = validate_iban(text)?;
+                Ok(Value::String(match format {
+                    IbanFormat::Electronic => iban,
+                    IbanFormat::Print => iban.as_bytes()
+                        .chunks(4)
+                        .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                }))
+            }
+            PaymentOperation::Bic { expand } => {
+                let mut bic = validate_bic(text)?;
+                if *expand && bic.len() == 8 {
+                    bic.push_str("XXX");
+                }
+                Ok(Value::String(bic))
+            }
+            PaymentOperation::CountryCode => {
+                let code = text.trim().to_uppercase();
+                if !country::is_country(&code) {
+                    return Err(invalid("ISO 3166 country code", text, "no assigned alpha-2 code at position 1".into()));
+                }
+                Ok(Value::String(code))
+            }
+            PaymentOperation::CurrencyCode => {
+                let code = text.trim().to_uppercase();
+                if currency::minor_units(&code).is_none() {
+                    return Err(invalid("ISO 4217 currency code", text, "no active code at position 1".into()));
+                }
+                Ok(Value::String(code))
+            }
+            PaymentOperation::IbanComponents(component) => {
+                let iban = validate_iban(text)?;
+                let (_, _, bank, branch) = iban_country(&iban[..2]).expect("validated IBAN country");
+                let bban = &iban[4..];
+                let components = json!({
+                    "countryCode": &iban[..2],
+                    "checkDigits": &iban[2..4],
+                    "bban": bban,
+                    "bankCode": &bban[bank.0..bank.1],
+                    "branchCode": branch.map(|(start, end)| &bban[start..end]),
+                });
+                Ok(match component {
+                    Some(component) => {
+                        let key = serde_json::to_value(component)?;
+                        components[key.as_str().unwrap_or_default()].clone()
+                    }
+                    None => components,
+                })
+            }
+        }
+    }
+}
+
+fn invalid(kind: &str, text: &str, reason: String) -> EnrichmentError {
+    EnrichmentError::ValidationError(format!("Invalid {} '{}': {}", kind, text, reason))
+}
+
+/// Strip spaces and dashes and uppercase, rejecting any other non-alphanumeric character at its position in `text`
+fn compact(kind: &str, text: &str) -> Result<String, EnrichmentError> {
+    let separator = |c: &char| c.is_whitespace() || *c == '-';
+    if let Some((i, c)) = text.chars().enumerate().find(|(_, c)| !separator(c) && !c.is_ascii_alphanumeric()) {
+        return Err(invalid(kind, text, format!("unexpected character '{}' at position {}", c, i + 1)));
+    }
+    Ok(text.chars().filter(|c| !separator(c)).collect::<String>().to_ascii_uppercase())
+}
+
+/// Error for a compacted identifier; positions count in the compacted form, which is quoted when it
+/// differs from what the caller wrote
+fn invalid_compacted(kind: &str, text: &str, compacted: &str, reason: String) -> EnrichmentError {
+    if text == compacted {
+        invalid(kind, text, reason)
+    } else {
+        invalid(kind, compacted, format!("{} (compacted from '{}')", reason, text))
+    }
+}
+
+fn iban_country(code: &str) -> Option<IbanCountry> {
+    IBAN_COUNTRIES.binary_search_by(|entry| entry.0.cmp(code)).ok().map(|index| IBAN_COUNTRIES[index])
+}
+
+/// Normalize an IBAN to its electronic form, checking country, length, check digits and the mod-97 checksum
+fn validate_iban(text: &str) -> Result<String, EnrichmentError> {
+    let iban = compact("IBAN", text)?;
+    let error = |reason: String| invalid_compacted("IBAN", text, &iban, reason);
+
+    if iban.len() < 4 {
+        return Err(error(format!("country code and check digits expected at positions 1 to 4, found {} characters", iban.len())));
+    }
+    let (_, length, _, _) = iban_country(&iban[..2])
+        .ok_or_else(|| error(format!("unknown IBAN country '{}' at position 1", &iban[..2])))?;
+    if let Some(i) = iban[2..4].find(|c: char| !c.is_ascii_digit()) {
+        return Err(error(format!("check digit expected at position {}", i + 3)));
+    }
+    if iban.len() < length {
+        return Err(error(format!("{} IBANs have {} characters, missing from position {}", &iban[..2], length, iban.len() + 1)));
+    }
+    if iban.len() > length {
+        return Err(error(format!("{} IBANs have {} characters, unexpected from position {}", &iban[..2], length, length + 1)));
+    }
+
+    // Move the country and check digits to the end and read letters as 10 to 35
+    let remainder = iban[4..].chars().chain(iban[..4].chars()).fold(0u32, |remainder, c| {
+        let digit = c.to_digit(36).unwrap_or_default();
+        if digit < 10 { (remainder * 10 + digit) % 97 } else { (remainder * 100 + digit) % 97 }
+    });
+    if remainder != 1 {
+        return Err(error("check digits at position 3 do not match (mod-97)".into()));
+    }
+    Ok(iban)
+}
+
+/// Normalize a BIC, checking the length, that the bank code is letters and the ISO 3166 country;
+/// the location and optional branch code are only required to be alphanumeric
+fn validate_bic(text: &str) -> Result<String, EnrichmentError> {
+    let bic = compact("BIC", text)?;
+    let error = |reason: String| invalid_compacted("BIC", text, &bic, reason);
+
+    if bic.len() != 8 && bic.len() != 11 {
+        return Err(error(format!("expected 8 or 11 characters, found {}", bic.len())));
+    }
+    if let Some(i) = bic[..4].find(|c: char| !c.is_ascii_alphabetic()) {
+        return Err(error(format!("bank code expects a letter at position {}", i + 1)));
+    }
+    // Kosovo uses the user-assigned code XK for BICs and IBANs
+    let country = &bic[4..6];
+    if !country::is_country(country) && country != "XK" {
+        return Err(error(format!("unknown country '{}' at position 5", country)));
+    }
+    Ok(bic)
+}
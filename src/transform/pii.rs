@@ -0,0 +1,175 @@
+use super::pattern::Pattern;
+use super::Transformer;
+use crate::error::EnrichmentError;
+use crate::provider::TokenVault;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Masking rules with preset defaults for common identifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum MaskPreset {
+    /// Card number masked to PCI DSS limits: first 6 and last 4 digits visible, separators kept
+    Pan,
+    /// Email address with only the first character of the local part visible
+    Email,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MaskParams {
+    pub preset: Option<MaskPreset>,
+    /// Leading characters left visible
+    pub keep_first: Option<usize>,
+    /// Trailing characters left visible, defaulting to 4 without a preset
+    pub keep_last: Option<usize>,
+    /// Character replacing hidden characters
+    #[serde(default = "default_mask_character")]
+    pub character: char,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct HashParams {
+    /// Name of a key registered on the engine; when set the hash is an HMAC-SHA256 instead of plain SHA-256
+    pub key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct RedactParams {
+    /// Redact only the matches of this pattern instead of the whole value
+    pub pattern: Option<Pattern>,
+    /// Text put in place of the redacted value or matches
+    #[serde(default = "default_redaction")]
+    pub replacement: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct TokenizeParams {
+    /// Vault namespace keeping tokens of different kinds of data apart
+    #[serde(default = "default_namespace")]
+    pub namespace: String,
+}
+
+fn default_mask_character() -> char {
+    '*'
+}
+
+fn default_redaction() -> String {
+    "[REDACTED]".into()
+}
+
+fn default_namespace() -> String {
+    "default".into()
+}
+
+pub enum PiiOperation<'a> {
+    Mask { preset: Option<MaskPreset>, keep_first: Option<usize>, keep_last: Option<usize>, character: char },
+    Hash { key: Option<&'a [u8]> },
+    Redact { pattern: Option<&'a Pattern>, replacement: String },
+    Tokenize { vault: &'a dyn TokenVault, namespace: String },
+}
+
+/// Transforms that hide personal data; `null` passes through unchanged
+pub struct PiiTransformer<'a> {
+    operation: PiiOperation<'a>,
+}
+
+impl<'a> PiiTransformer<'a> {
+    pub fn new(operation: PiiOperation<'a>) -> Self {
+        Self { operation }
+    }
+}
+
+impl Transformer for PiiTransformer<'_> {
+    fn transform(&self, value: &Value) -> Result<Value, EnrichmentError> {
+        let text = match value {
+            Value::Null => return Ok(Value::Null),
+            Value::String(s) => s.clone(),
+            Value::Number(_) | Value::Bool(_) => value.to_string(),
+            _ if matches!(self.operation, PiiOperation::Redact { pattern: None, .. }) => String::new(),
+            _ => return Err(EnrichmentError::TransformError("Value is not a scalar".into())),
+        };
+
+        match &self.operation {
+            PiiOperation::Mask { preset, keep_first, keep_last, character } => {
+                let masked = match preset {
+                    Some(MaskPreset::Pan) => mask_pan(&text, *keep_first, *keep_last, *character)?,
+                    Some(MaskPreset::Email) => mask_email(&text, *keep_first, *keep_last, *character)?,
+                    None => mask(&text, keep_first.unwrap_or(0), keep_last.unwrap_or(4), *character),
+                };
+                Ok(Value::String(masked))
+            }
+            PiiOperation::Hash { key } => {
+                let digest = match key {
+                    Some(key) => {
+                        let mut mac = Hmac::<Sha256>::new_from_slice(key)
+                            .map_err(|e| EnrichmentError::SpecError(format!("Invalid hash key: {}", e)))?;
+                        mac.update(text.as_bytes());
+                        mac.finalize().into_bytes().to_vec()
+                    }
+                    None => Sha256::digest(text.as_bytes()).to_vec(),
+                };
+                Ok(Value::String(hex::encode(digest)))
+            }
+            PiiOperation::Redact { pattern, replacement } => match pattern {
+                Some(pattern) => Ok(Value::String(
+                    pattern.regex().replace_all(&text, regex::NoExpand(replacement)).into_owned(),
+                )),
+                None => Ok(Value::String(replacement.clone())),
+            },
+            PiiOperation::Tokenize { vault, namespace } => Ok(Value::String(vault.tokenize(namespace, &text)?)),
+        }
+    }
+}
+
+/// Replace every grapheme except the first and last ones; values too short to keep anything hidden are masked entirely
+fn mask(text: &str, keep_first: usize, keep_last: usize, character: char) -> String {
+    let graphemes: Vec<&str> = text.graphemes(true).collect();
+    if keep_first.saturating_add(keep_last) >= graphemes.len() {
+        return character.to_string().repeat(graphemes.len());
+    }
+    graphemes.iter()
+        .enumerate()
+        .map(|(i, g)| if i < keep_first || i >= graphemes.len() - keep_last { g.to_string() } else { character.to_string() })
+        .collect()
+}
+
+fn mask_pan(text: &str, keep_first: Option<usize>, keep_last: Option<usize>, character: char) -> Result<String, EnrichmentError> {
+    let digits = text.chars().filter(char::is_ascii_digit).count();
+    let valid = (12..=19).contains(&digits) && text.chars().all(|c| c.is_ascii_digit() || c == ' ' || c == '-');
+    if !valid {
+        return Err(EnrichmentError::TransformError("Value is not a card number".into()));
+    }
+
+    // PCI DSS allows at most the first 6 and last 4 digits to be displayed
+    let keep_first = keep_first.unwrap_or(6).min(6);
+    let keep_last = keep_last.unwrap_or(4).min(4);
+    let mut position = 0;
+    Ok(text.chars()
+        .map(|c| {
+            if !c.is_ascii_digit() {
+                return c;
+            }
+            position += 1;
+            if position <= keep_first || position > digits - keep_last { c } else { character }
+        })
+        .collect())
+}
+
+fn mask_email(text: &str, keep_first: Option<usize>, keep_last: Option<usize>, character: char) -> Result<String, EnrichmentError> {
+    let (local, domain) = text.rsplit_once('@')
+        .filter(|(local, domain)| !local.is_empty() && !domain.is_empty())
+        .ok_or_else(|| EnrichmentError::TransformError("Value is not an email address".into()))?;
+    Ok(format!("{}@{}", mask(local, keep_first.unwrap_or(1), keep_last.unwrap_or(0), character), domain))
+}
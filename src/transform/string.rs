@@ -1,20 +1,302 @@
+use super::template::MAX_WIDTH;
 use super::Transformer;
 use crate::error::EnrichmentError;
+use serde::{de, Deserialize, Deserializer, Serialize};
 use serde_json::Value;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
 
-pub struct StringTransformer;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ReplaceParams {
+    /// Substring to search for
+    pub from: String,
+    /// Replacement for every occurrence
+    #[serde(default)]
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct SubstringParams {
+    /// Index of the first character to keep
+    #[serde(default)]
+    pub start: usize,
+    /// Number of characters to keep, defaulting to the rest of the string
+    pub length: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct TrimParams {
+    /// Characters to remove, defaulting to whitespace
+    pub characters: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct PadParams {
+    /// Minimum length in graphemes, at most 1024
+    #[serde(deserialize_with = "pad_length")]
+    #[cfg_attr(feature = "schemars", schemars(range(max = 1024)))]
+    pub length: usize,
+    /// Non-empty text repeated to fill the gap
+    #[serde(default = "default_fill", deserialize_with = "pad_fill")]
+    #[cfg_attr(feature = "schemars", schemars(length(min = 1)))]
+    pub fill: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct TruncateParams {
+    /// Maximum length in graphemes, including the ellipsis
+    pub length: usize,
+    /// Marker appended when the text is cut
+    #[serde(default = "default_ellipsis")]
+    pub ellipsis: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct NormalizeParams {
+    #[serde(default)]
+    pub form: NormalizationForm,
+}
+
+/// Unicode normalization form
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "UPPERCASE")]
+pub enum NormalizationForm {
+    /// Canonical composition
+    #[default]
+    #[serde(alias = "nfc")]
+    Nfc,
+    /// Canonical decomposition
+    #[serde(alias = "nfd")]
+    Nfd,
+    /// Compatibility composition, folding variants such as ligatures and full-width forms
+    #[serde(alias = "nfkc")]
+    Nfkc,
+    /// Compatibility decomposition
+    #[serde(alias = "nfkd")]
+    Nfkd,
+}
+
+fn default_fill() -> String {
+    " ".into()
+}
+
+fn pad_length<'de, D: Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+    let length = usize::deserialize(deserializer)?;
+    if length > MAX_WIDTH {
+        return Err(de::Error::custom(format!("pad length {} exceeds {}", length, MAX_WIDTH)));
+    }
+    Ok(length)
+}
+
+fn pad_fill<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let fill = String::deserialize(deserializer)?;
+    if fill.is_empty() {
+        return Err(de::Error::custom("pad fill must not be empty"));
+    }
+    Ok(fill)
+}
+
+fn default_ellipsis() -> String {
+    "...".into()
+}
+
+/// Word joining style for case conversions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseStyle {
+    Title,
+    Camel,
+    Snake,
+    Kebab,
+}
+
+pub enum StringOperation {
+    ToString,
+    ToUpperCase,
+    ToLowerCase,
+    Replace { from: String, to: String },
+    Substring { start: usize, length: Option<usize> },
+    Trim { start: bool, end: bool, characters: Option<String> },
+    PadLeft { length: usize, fill: String },
+    PadRight { length: usize, fill: String },
+    Truncate { length: usize, ellipsis: String },
+    Normalize(NormalizationForm),
+    StripDiacritics,
+    CollapseWhitespace,
+    Case(CaseStyle),
+}
+
+pub struct StringTransformer {
+    operation: StringOperation,
+}
 
 impl StringTransformer {
-    pub fn new() -> Self {
-        StringTransformer
+    pub fn new(operation: StringOperation) -> Self {
+        Self { operation }
     }
 }
 
 impl Transformer for StringTransformer {
     fn transform(&self, value: &Value) -> Result<Value, EnrichmentError> {
-        match value {
-            Value::String(s) => Ok(Value::String(s.to_uppercase())),
+        // Every operation except toString needs a string input
+        let text = match value {
+            Value::String(s) => Ok(s),
             _ => Err(EnrichmentError::TransformError("Value is not a string".into())),
+        };
+
+        match &self.operation {
+            StringOperation::ToString => match value {
+                Value::String(_) => Ok(value.clone()),
+                Value::Null => Ok(Value::String(String::new())),
+                other => Ok(Value::String(other.to_string())),
+            },
+            StringOperation::ToUpperCase => Ok(Value::String(text?.to_uppercase())),
+            StringOperation::ToLowerCase => Ok(Value::String(text?.to_lowercase())),
+            StringOperation::Replace { from, to } => Ok(Value::String(text?.replace(from.as_str(), to))),
+            StringOperation::Substring { start, length } => {
+                let chars = text?.chars().skip(*start);
+                let result = match length {
+                    Some(length) => chars.take(*length).collect(),
+                    None => chars.collect(),
+                };
+                Ok(Value::String(result))
+            }
+            StringOperation::Trim { start, end, characters } => {
+                let strip = |c: char| match characters {
+                    Some(characters) => characters.contains(c),
+                    None => c.is_whitespace(),
+                };
+                let mut result = text?.as_str();
+                if *start {
+                    result = result.trim_start_matches(strip);
+                }
+                if *end {
+                    result = result.trim_end_matches(strip);
+                }
+                Ok(Value::String(result.to_string()))
+            }
+            StringOperation::PadLeft { length, fill } => {
+                let s = text?;
+                Ok(Value::String(format!("{}{}", padding(s, *length, fill), s)))
+            }
+            StringOperation::PadRight { length, fill } => {
+                let s = text?;
+                Ok(Value::String(format!("{}{}", s, padding(s, *length, fill))))
+            }
+            StringOperation::Truncate { length, ellipsis } => {
+                let graphemes: Vec<&str> = text?.graphemes(true).collect();
+                if graphemes.len() <= *length {
+                    return Ok(value.clone());
+                }
+                // An ellipsis longer than the limit is itself cut, so the result never exceeds `length`
+                let ellipsis: Vec<&str> = ellipsis.graphemes(true).take(*length).collect();
+                let keep = length - ellipsis.len();
+                Ok(Value::String(format!("{}{}", graphemes[..keep].concat(), ellipsis.concat())))
+            }
+            StringOperation::Normalize(form) => {
+                let s = text?;
+                Ok(Value::String(match form {
+                    NormalizationForm::Nfc => s.nfc().collect(),
+                    NormalizationForm::Nfd => s.nfd().collect(),
+                    NormalizationForm::Nfkc => s.nfkc().collect(),
+                    NormalizationForm::Nfkd => s.nfkd().collect(),
+                }))
+            }
+            StringOperation::StripDiacritics => {
+                Ok(Value::String(text?.nfd().filter(|c| !is_combining_mark(*c)).nfc().collect()))
+            }
+            StringOperation::CollapseWhitespace => {
+                Ok(Value::String(text?.split_whitespace().collect::<Vec<_>>().join(" ")))
+            }
+            StringOperation::Case(style) => Ok(Value::String(convert_case(text?, *style))),
+        }
+    }
+}
+
+/// Fill needed to bring the text up to `length` graphemes, cycling through the fill's graphemes
+fn padding(text: &str, length: usize, fill: &str) -> String {
+    let missing = length.saturating_sub(text.graphemes(true).count());
+    fill.graphemes(true).cycle().take(missing).collect()
+}
+
+/// Split text into words at non-alphanumeric characters and at case changes,
+/// keeping acronyms together so `HTTPServer` yields `HTTP` and `Server`
+fn words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let graphemes: Vec<&str> = text.graphemes(true).collect();
+    let first_char = |g: &str| g.chars().next().unwrap_or(' ');
+
+    let mut previous: Option<char> = None;
+
+    for (i, grapheme) in graphemes.iter().enumerate() {
+        let c = first_char(grapheme);
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous = None;
+            continue;
+        }
+        if let Some(previous) = previous {
+            let next_lower = graphemes.get(i + 1).is_some_and(|g| first_char(g).is_lowercase());
+            let boundary = c.is_uppercase() && (previous.is_lowercase() || (previous.is_uppercase() && next_lower));
+            if boundary {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push_str(grapheme);
+        previous = Some(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut graphemes = word.graphemes(true);
+    match graphemes.next() {
+        Some(first) => format!("{}{}", first.to_uppercase(), graphemes.as_str().to_lowercase()),
+        None => String::new(),
+    }
+}
+
+fn convert_case(text: &str, style: CaseStyle) -> String {
+    match style {
+        // Title case keeps the original separators and only recases each word
+        CaseStyle::Title => {
+            let mut result = String::with_capacity(text.len());
+            let mut word = String::new();
+            for grapheme in text.graphemes(true) {
+                if grapheme.chars().next().is_some_and(char::is_alphanumeric) {
+                    word.push_str(grapheme);
+                } else {
+                    result.push_str(&capitalize(&std::mem::take(&mut word)));
+                    result.push_str(grapheme);
+                }
+            }
+            result.push_str(&capitalize(&word));
+            result
         }
+        CaseStyle::Camel => words(text).iter()
+            .enumerate()
+            .map(|(i, word)| if i == 0 { word.to_lowercase() } else { capitalize(word) })
+            .collect(),
+        CaseStyle::Snake => words(text).iter().map(|word| word.to_lowercase()).collect::<Vec<_>>().join("_"),
+        CaseStyle::Kebab => words(text).iter().map(|word| word.to_lowercase()).collect::<Vec<_>>().join("-"),
     }
 }
@@ -1,31 +1,361 @@
+use super::numeric::{number_to_decimal, parse_decimal};
 use super::Transformer;
 use crate::error::EnrichmentError;
-use serde_json::Value;
+use crate::types::JsonPath;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
 
-pub struct TemplateTransformer {
-    template: String,
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct TemplateParams {
+    /// Text with `{0}`, `{name}`, `{name:spec}`, `{name?default}`, `{#name}...{/name}` and `${var}` placeholders;
+    /// `{{` and `}}` are literal braces and `$${` is a literal `$` before a placeholder
+    pub template: Template,
+    /// Sources bound to named placeholders, taking precedence over fields of the input value
+    #[serde(default)]
+    pub bindings: BTreeMap<String, JsonPath>,
 }
 
-impl TemplateTransformer {
-    pub fn new(template: String) -> Self {
-        Self { template }
+/// A template parsed when the spec is loaded, so syntax errors fail at parse time
+#[derive(Debug, Clone)]
+pub struct Template {
+    source: String,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Text(String),
+    Variable(String),
+    Field { path: Vec<String>, spec: FormatSpec, default: Option<String> },
+    Section { path: Vec<String>, inverted: bool, body: Vec<Segment> },
+}
+
+/// Largest padded width of a placeholder or pad transform, so a typo cannot allocate gigabytes of padding
+pub(crate) const MAX_WIDTH: usize = 1024;
+
+#[derive(Debug, Clone, Default)]
+struct FormatSpec {
+    fill: Option<char>,
+    align: Option<char>,
+    zero: bool,
+    width: usize,
+    precision: Option<u32>,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self, EnrichmentError> {
+        let mut stack: Vec<(Vec<String>, bool, Vec<Segment>)> = Vec::new();
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut chars = source.chars().peekable();
+
+        let error = |message: String| EnrichmentError::SpecError(format!("Invalid template '{}': {}", source, message));
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '}' => return Err(error("unmatched '}', write '}}' for a literal brace".into())),
+                '$' if chars.peek() == Some(&'$') && chars.clone().nth(1) == Some('{') => {
+                    chars.next();
+                    text.push('$');
+                }
+                '$' if chars.peek() == Some(&'{') && chars.clone().nth(1) != Some('{') => {
+                    chars.next();
+                    let name = read_tag(&mut chars)
+                        .ok_or_else(|| error("unterminated variable reference".into()))?;
+                    if name.is_empty() {
+                        return Err(error("empty variable reference".into()));
+                    }
+                    flush(&mut text, &mut segments);
+                    segments.push(Segment::Variable(name));
+                }
+                '{' => {
+                    let tag = read_tag(&mut chars)
+                        .ok_or_else(|| error("unterminated placeholder".into()))?;
+                    flush(&mut text, &mut segments);
+
+                    if let Some(name) = tag.strip_prefix('#').or_else(|| tag.strip_prefix('^')) {
+                        let inverted = tag.starts_with('^');
+                        stack.push((field_path(name).map_err(error)?, inverted, std::mem::take(&mut segments)));
+                    } else if let Some(name) = tag.strip_prefix('/') {
+                        let path = field_path(name).map_err(error)?;
+                        let (open, inverted, outer) = stack.pop()
+                            .ok_or_else(|| error(format!("'{{/{}}}' closes no section", name)))?;
+                        if open != path {
+                            return Err(error(format!("'{{/{}}}' closes section '{}'", name, open.join("."))));
+                        }
+                        let body = std::mem::replace(&mut segments, outer);
+                        segments.push(Segment::Section { path, inverted, body });
+                    } else {
+                        let (field, default) = match tag.split_once('?') {
+                            Some((field, default)) => (field, Some(default.to_string())),
+                            None => (tag.as_str(), None),
+                        };
+                        let (name, spec) = match field.split_once(':') {
+                            Some((name, spec)) => (name, FormatSpec::parse(spec).map_err(error)?),
+                            None => (field, FormatSpec::default()),
+                        };
+                        segments.push(Segment::Field { path: field_path(name).map_err(error)?, spec, default });
+                    }
+                }
+                c => text.push(c),
+            }
+        }
+
+        if let Some((open, _, _)) = stack.last() {
+            return Err(error(format!("section '{}' is never closed", open.join("."))));
+        }
+        flush(&mut text, &mut segments);
+        Ok(Self { source: source.to_string(), segments })
+    }
+
+    /// Names of the `${var}` references in the template
+    pub fn variables(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        collect_variables(&self.segments, &mut names);
+        names
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
     }
 }
 
-impl Transformer for TemplateTransformer {
-    fn transform(&self, value: &Value) -> Result<Value, EnrichmentError> {
-        match value {
-            Value::Array(values) => {
-                let mut result = self.template.clone();
-                for (i, val) in values.iter().enumerate() {
-                    let placeholder = format!("{{{}}}", i);
-                    let value_str = val.as_str()
-                        .ok_or_else(|| EnrichmentError::TransformError("Array value is not a string".into()))?;
-                    result = result.replace(&placeholder, value_str);
+/// Read up to the closing `}`, or `None` if the template ends first
+fn read_tag(chars: &mut impl Iterator<Item = char>) -> Option<String> {
+    let mut tag = String::new();
+    for c in chars {
+        if c == '}' {
+            return Some(tag);
+        }
+        tag.push(c);
+    }
+    None
+}
+
+fn flush(text: &mut String, segments: &mut Vec<Segment>) {
+    if !text.is_empty() {
+        segments.push(Segment::Text(std::mem::take(text)));
+    }
+}
+
+fn field_path(name: &str) -> Result<Vec<String>, String> {
+    let name = name.trim();
+    let valid = !name.is_empty()
+        && name.split('.').all(|part| !part.is_empty() && part.chars().all(|c| c.is_alphanumeric() || c == '_'));
+    if valid {
+        Ok(name.split('.').map(String::from).collect())
+    } else {
+        Err(format!("invalid placeholder name '{}'", name))
+    }
+}
+
+fn collect_variables<'a>(segments: &'a [Segment], names: &mut Vec<&'a str>) {
+    for segment in segments {
+        match segment {
+            Segment::Variable(name) => names.push(name),
+            Segment::Section { body, .. } => collect_variables(body, names),
+            _ => {}
+        }
+    }
+}
+
+impl FormatSpec {
+    /// Parse `[[fill]align][0][width][.precision]`, where align is `<`, `>` or `^`
+    fn parse(spec: &str) -> Result<Self, String> {
+        let mut result = FormatSpec::default();
+        let chars: Vec<char> = spec.chars().collect();
+        let mut i = 0;
+        if chars.len() >= 2 && matches!(chars[1], '<' | '>' | '^') {
+            result.fill = Some(chars[0]);
+            result.align = Some(chars[1]);
+            i = 2;
+        } else if chars.first().is_some_and(|c| matches!(c, '<' | '>' | '^')) {
+            result.align = Some(chars[0]);
+            i = 1;
+        }
+        if chars.get(i) == Some(&'0') {
+            result.zero = true;
+            i += 1;
+        }
+        let rest: String = chars[i..].iter().collect();
+        let (width, precision) = match rest.split_once('.') {
+            Some((width, precision)) => (width, Some(precision)),
+            None => (rest.as_str(), None),
+        };
+        if !width.is_empty() {
+            result.width = width.parse().map_err(|_| format!("invalid width in format '{}'", spec))?;
+            if result.width > MAX_WIDTH {
+                return Err(format!("width in format '{}' exceeds {}", spec, MAX_WIDTH));
+            }
+        }
+        if let Some(precision) = precision {
+            result.precision = Some(precision.parse().map_err(|_| format!("invalid precision in format '{}'", spec))?);
+        }
+        Ok(result)
+    }
+
+    fn apply(&self, value: &Value) -> Result<String, EnrichmentError> {
+        let numeric = match value {
+            Value::Number(n) => Some(number_to_decimal(n)?),
+            Value::String(s) if self.precision.is_some() => parse_decimal(s.trim()).ok(),
+            _ => None,
+        };
+        let mut text = match (numeric, self.precision) {
+            (Some(number), Some(places)) => {
+                let mut rounded = number.round_dp(places);
+                rounded.rescale(places);
+                rounded.to_string()
+            }
+            (None, Some(places)) => stringify(value).chars().take(places as usize).collect(),
+            (_, None) => stringify(value),
+        };
+
+        let len = text.chars().count();
+        if len < self.width {
+            let padding = self.width - len;
+            if self.zero && self.align.is_none() {
+                let sign = if text.starts_with('-') { text.remove(0).to_string() } else { String::new() };
+                text = format!("{}{}{}", sign, "0".repeat(padding), text);
+            } else {
+                let fill = self.fill.unwrap_or(' ').to_string();
+                let align = self.align.unwrap_or(if numeric.is_some() { '>' } else { '<' });
+                text = match align {
+                    '>' => format!("{}{}", fill.repeat(padding), text),
+                    '^' => format!("{}{}{}", fill.repeat(padding / 2), text, fill.repeat(padding - padding / 2)),
+                    _ => format!("{}{}", text, fill.repeat(padding)),
+                };
+            }
+        }
+        Ok(text)
+    }
+}
+
+impl Serialize for Template {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for Template {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let source = String::deserialize(deserializer)?;
+        Template::parse(&source).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Template {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Template".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({ "type": "string" })
+    }
+}
+
+/// Renders a parsed template against the input value, named bindings and variables.
+/// Placeholders are looked up in the bindings first, then in the input: by index when it is an array,
+/// by key when it is an object, and as `{0}` when it is a scalar.
+pub struct TemplateTransformer<'a> {
+    template: &'a Template,
+    bindings: Map<String, Value>,
+    vars: HashMap<String, Value>,
+}
+
+impl<'a> TemplateTransformer<'a> {
+    pub fn new(template: &'a Template, bindings: Map<String, Value>, vars: HashMap<String, Value>) -> Self {
+        Self { template, bindings, vars }
+    }
+
+    fn lookup<'v>(&'v self, path: &[String], input: &'v Value) -> Option<&'v Value> {
+        let (first, rest) = path.split_first()?;
+        let mut current = match self.bindings.get(first) {
+            Some(bound) => bound,
+            None => match input {
+                Value::Array(values) => values.get(first.parse::<usize>().ok()?)?,
+                Value::Object(fields) => fields.get(first)?,
+                scalar if first == "0" => scalar,
+                _ => return None,
+            },
+        };
+        for key in rest {
+            current = match current {
+                Value::Object(fields) => fields.get(key)?,
+                Value::Array(values) => values.get(key.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
+        }
+        Some(current).filter(|value| !value.is_null())
+    }
+
+    fn render(&self, segments: &[Segment], input: &Value, output: &mut String) -> Result<(), EnrichmentError> {
+        for segment in segments {
+            match segment {
+                Segment::Text(text) => output.push_str(text),
+                Segment::Variable(name) => match self.vars.get(name) {
+                    Some(value) => output.push_str(&stringify(value)),
+                    None => return Err(EnrichmentError::SpecError(format!("Unknown variable: {}", name))),
+                },
+                Segment::Field { path, spec, default } => match (self.lookup(path, input), default) {
+                    (Some(value), _) => output.push_str(&spec.apply(value)?),
+                    (None, Some(default)) => output.push_str(default),
+                    (None, None) => {
+                        return Err(EnrichmentError::TransformError(format!(
+                            "Template placeholder {{{}}} has no value", path.join(".")
+                        )))
+                    }
+                },
+                Segment::Section { path, inverted, body } => {
+                    let present = self.lookup(path, input).is_some_and(|value| match value {
+                        Value::Bool(b) => *b,
+                        Value::String(s) => !s.is_empty(),
+                        Value::Array(values) => !values.is_empty(),
+                        _ => true,
+                    });
+                    if present != *inverted {
+                        self.render(body, input, output)?;
+                    }
                 }
-                Ok(Value::String(result))
             }
-            _ => Err(EnrichmentError::TransformError("Value is not an array".into())),
         }
+        Ok(())
+    }
+}
+
+impl Transformer for TemplateTransformer<'_> {
+    fn transform(&self, value: &Value) -> Result<Value, EnrichmentError> {
+        let mut output = String::new();
+        self.render(&self.template.segments, value, &mut output)?;
+        Ok(Value::String(output))
     }
-}
\ No newline at end of file
+}
+
+/// Text form of a value: strings without quotes, other values as JSON
+fn stringify(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
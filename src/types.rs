@@ -1,44 +1,382 @@
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::transform::{
+    AddBusinessDaysParams, AddDaysParams, ArithmeticParams, AtParams, Base64Params, BicParams, ConcatParams,
+    ConvertTimezoneParams, DateDiffParams, FilterParams, FlattenParams, FormatDateParams, FormatMoneyParams,
+    HashParams, HexParams, IbanComponentsParams, IbanParams, KeysParams, LookupParams, MajorUnitsParams, MapParams,
+    MaskParams, MergeParams, MinorUnitsParams, NormalizeParams, PadParams, ParseDateParams, ParseJsonParams,
+    ParseMoneyParams, RedactParams, RegexCapturesParams, RegexExtractParams, RegexMatchParams, RegexReplaceParams,
+    ReplaceParams, RoundParams, ScaleParams, SliceParams, SortParams, SplitParams, StringifyJsonParams,
+    SubstringParams, SumMoneyParams, TemplateParams, ToBooleanParams, ToIntegerParams, ToNullParams, TokenizeParams,
+    TrimParams, TruncateParams,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Spec {
+    /// Variables referenced as `${name}` in paths, params and templates
+    #[serde(default)]
+    pub vars: HashMap<String, serde_json::Value>,
+    pub rules: Vec<MappingRule>,
+}
+
+impl From<Vec<MappingRule>> for Spec {
+    fn from(rules: Vec<MappingRule>) -> Self {
+        Self { vars: HashMap::new(), rules }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MappingRule {
+    /// JSONPath(s) or computed source providing the value
     pub source: JsonPath,
+    /// Field path(s) receiving the value
     pub target: Target,
+    /// Transformation applied to the source value before it is set
     pub transform: Option<Transform>,
+    /// Reserved for a JSONLogic condition guarding the rule; accepted but not evaluated yet, so the rule always runs
     pub condition: Option<serde_json::Value>,
+    /// How the value combines with what the target already holds
+    #[serde(default)]
+    pub mode: WriteMode,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum JsonPath {
     Single(String),
     Multiple(Vec<String>),
+    Const {
+        #[serde(rename = "const")]
+        value: serde_json::Value,
+    },
+    Now {
+        now: NowSource,
+    },
+    Uuid {
+        uuid: UuidSource,
+    },
+    Sequence {
+        sequence: String,
+    },
+    Context {
+        context: String,
+    },
+    /// An object whose values are read from their own sources
+    Object {
+        object: BTreeMap<String, JsonPath>,
+    },
+    /// The first of the paths holding a non-null value, falling back to `default` and then `null`
+    Coalesce {
+        coalesce: Vec<String>,
+        default: Option<serde_json::Value>,
+    },
+}
+
+/// Format of a `now` source: a strftime pattern, `rfc3339` (default), `epochSeconds` or `epochMillis`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(default, deny_unknown_fields)]
+pub struct NowSource {
+    pub format: Option<String>,
+}
+
+/// Format of a `uuid` source: hyphenated by default, or without hyphens when `simple` is set
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(default, deny_unknown_fields)]
+pub struct UuidSource {
+    pub simple: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Target {
     Single(String),
     Multiple(Vec<String>),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Transform {
-    #[serde(rename = "type")]
-    pub transform_type: TransformType,
-    pub params: Option<HashMap<String, String>>,
+/// How a rule's value combines with the current value of its target
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum WriteMode {
+    /// Replace the current value
+    #[default]
+    Overwrite,
+    /// Set the value only when the target is `null`, `""`, `0`, `false`, `[]` or `{}`
+    IfEmpty,
+    /// Push onto an array, or concatenate onto a string
+    Append,
+    /// Deep-merge an object into the current object
+    Merge,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub enum TransformType {
-    ToString,
-    ToUpperCase,
-    ToLowerCase,
-    Split,
-    Concat,
-    Replace,
-    Substring,
-    Template,
-}
\ No newline at end of file
+/// Transformation tagged by `type`, with its params either inline or nested under `params`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(transform = crate::schema::accept_nested_params))]
+#[serde(tag = "type", rename_all = "camelCase", deny_unknown_fields, remote = "Self")]
+pub enum Transform {
+    ToString {},
+    ToUpperCase {},
+    ToLowerCase {},
+    Split(SplitParams),
+    Concat(ConcatParams),
+    Replace(ReplaceParams),
+    Substring(SubstringParams),
+    Template(TemplateParams),
+    Trim(TrimParams),
+    TrimStart(TrimParams),
+    TrimEnd(TrimParams),
+    PadLeft(PadParams),
+    PadRight(PadParams),
+    Truncate(TruncateParams),
+    Normalize(NormalizeParams),
+    StripDiacritics {},
+    CollapseWhitespace {},
+    TitleCase {},
+    CamelCase {},
+    SnakeCase {},
+    KebabCase {},
+    ToNumber {},
+    ToInteger(ToIntegerParams),
+    Round(RoundParams),
+    Scale(ScaleParams),
+    Add(ArithmeticParams),
+    Subtract(ArithmeticParams),
+    Multiply(ArithmeticParams),
+    Divide(ArithmeticParams),
+    Abs {},
+    ParseMoney(ParseMoneyParams),
+    FormatMoney(FormatMoneyParams),
+    ToMinorUnits(MinorUnitsParams),
+    ToMajorUnits(MajorUnitsParams),
+    SumMoney(SumMoneyParams),
+    ParseDate(ParseDateParams),
+    FormatDate(FormatDateParams),
+    ConvertTimezone(ConvertTimezoneParams),
+    AddDays(AddDaysParams),
+    AddBusinessDays(AddBusinessDaysParams),
+    DateDiff(DateDiffParams),
+    RegexMatch(RegexMatchParams),
+    RegexExtract(RegexExtractParams),
+    RegexCaptures(RegexCapturesParams),
+    RegexReplace(RegexReplaceParams),
+    ToBoolean(ToBooleanParams),
+    ToNull(ToNullParams),
+    Lookup(LookupParams),
+    First {},
+    Last {},
+    At(AtParams),
+    Slice(SliceParams),
+    Filter(FilterParams),
+    Map(MapParams),
+    Sort(SortParams),
+    Unique {},
+    Flatten(FlattenParams),
+    Count {},
+    Sum {},
+    Pick(KeysParams),
+    Omit(KeysParams),
+    Merge(MergeParams),
+    Entries {},
+    FromEntries {},
+    Mask(MaskParams),
+    Hash(HashParams),
+    Redact(RedactParams),
+    Tokenize(TokenizeParams),
+    Base64Encode(Base64Params),
+    Base64Decode(Base64Params),
+    HexEncode(HexParams),
+    HexDecode {},
+    UrlEncode {},
+    UrlDecode {},
+    ParseJson(ParseJsonParams),
+    StringifyJson(StringifyJsonParams),
+    Iban(IbanParams),
+    Bic(BicParams),
+    CountryCode {},
+    CurrencyCode {},
+    IbanComponents(IbanComponentsParams),
+}
+
+impl<'de> Deserialize<'de> for Transform {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut fields = serde_json::Map::deserialize(deserializer)?;
+
+        // Accept any spelling of the type name and rewrite it to the canonical one
+        let name = match fields.get("type") {
+            Some(serde_json::Value::String(name)) => name,
+            Some(_) => return Err(de::Error::custom("transform type must be a string")),
+            None => return Err(de::Error::missing_field("type")),
+        };
+        let canonical = canonical_transform_name(name).map_err(de::Error::custom)?;
+        fields.insert("type".into(), canonical.into());
+
+        // Lift params nested under `params` up next to `type`
+        match fields.remove("params") {
+            Some(serde_json::Value::Object(params)) => {
+                for (name, value) in params {
+                    if fields.contains_key(&name) {
+                        return Err(de::Error::custom(format!("duplicate param `{}`", name)));
+                    }
+                    fields.insert(name, value);
+                }
+            }
+            Some(serde_json::Value::Null) | None => {}
+            Some(_) => return Err(de::Error::custom("transform params must be an object")),
+        }
+
+        Transform::deserialize(serde_json::Value::Object(fields)).map_err(de::Error::custom)
+    }
+}
+
+/// Canonical transform names with the aliases accepted for each
+pub(crate) const TRANSFORM_NAMES: &[(&str, &[&str])] = &[
+    ("toString", &["string"]),
+    ("toUpperCase", &["upper", "uppercase"]),
+    ("toLowerCase", &["lower", "lowercase"]),
+    ("split", &[]),
+    ("concat", &["join"]),
+    ("replace", &[]),
+    ("substring", &["substr"]),
+    ("template", &["format"]),
+    ("trim", &[]),
+    ("trimStart", &["trimLeft", "ltrim"]),
+    ("trimEnd", &["trimRight", "rtrim"]),
+    ("padLeft", &["padStart", "lpad"]),
+    ("padRight", &["padEnd", "rpad"]),
+    ("truncate", &[]),
+    ("normalize", &["unicodeNormalize"]),
+    ("stripDiacritics", &["removeDiacritics", "removeAccents"]),
+    ("collapseWhitespace", &["squish"]),
+    ("titleCase", &["toTitleCase"]),
+    ("camelCase", &["toCamelCase"]),
+    ("snakeCase", &["toSnakeCase"]),
+    ("kebabCase", &["toKebabCase"]),
+    ("toNumber", &["number", "parseNumber"]),
+    ("toInteger", &["integer", "int"]),
+    ("round", &[]),
+    ("scale", &[]),
+    ("add", &["plus"]),
+    ("subtract", &["minus"]),
+    ("multiply", &["times"]),
+    ("divide", &[]),
+    ("abs", &["absolute"]),
+    ("parseMoney", &["parseAmount"]),
+    ("formatMoney", &["formatAmount"]),
+    ("toMinorUnits", &["minorUnits"]),
+    ("toMajorUnits", &["majorUnits"]),
+    ("sumMoney", &["sumAmounts"]),
+    ("parseDate", &[]),
+    ("formatDate", &[]),
+    ("convertTimezone", &["toTimezone"]),
+    ("addDays", &[]),
+    ("addBusinessDays", &[]),
+    ("dateDiff", &[]),
+    ("regexMatch", &["matches"]),
+    ("regexExtract", &["extract"]),
+    ("regexCaptures", &["captures"]),
+    ("regexReplace", &[]),
+    ("toBoolean", &["boolean", "bool", "toBool"]),
+    ("toNull", &["nullIf"]),
+    ("lookup", &["mapValue"]),
+    ("first", &[]),
+    ("last", &[]),
+    ("at", &["index", "nth"]),
+    ("slice", &[]),
+    ("filter", &[]),
+    ("map", &["each"]),
+    ("sort", &[]),
+    ("unique", &["distinct", "dedupe"]),
+    ("flatten", &[]),
+    ("count", &["length", "len"]),
+    ("sum", &["total"]),
+    ("pick", &[]),
+    ("omit", &[]),
+    ("merge", &[]),
+    ("entries", &["toEntries"]),
+    ("fromEntries", &[]),
+    ("mask", &[]),
+    ("hash", &["sha256", "hmac"]),
+    ("redact", &[]),
+    ("tokenize", &["tokenise"]),
+    ("base64Encode", &["toBase64"]),
+    ("base64Decode", &["fromBase64"]),
+    ("hexEncode", &["toHex"]),
+    ("hexDecode", &["fromHex"]),
+    ("urlEncode", &["percentEncode"]),
+    ("urlDecode", &["percentDecode"]),
+    ("parseJson", &["fromJson", "jsonParse"]),
+    ("stringifyJson", &["toJson", "jsonStringify"]),
+    ("iban", &["normalizeIban", "validateIban"]),
+    ("bic", &["swift", "normalizeBic", "validateBic"]),
+    ("countryCode", &["iso3166"]),
+    ("currencyCode", &["iso4217"]),
+    ("ibanComponents", &["parseIban"]),
+];
+
+/// Drop `_` and `-` and lowercase a name so `ToUpperCase`, `to_upper_case` and `toUpperCase` compare equal;
+/// other separators are kept so names the spec schema rejects are rejected here too
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '_' | '-'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn canonical_transform_name(name: &str) -> Result<&'static str, String> {
+    let normalized = normalize_name(name);
+    let candidates = TRANSFORM_NAMES.iter()
+        .flat_map(|(canonical, aliases)| std::iter::once(canonical).chain(aliases.iter()).map(move |alias| (*canonical, *alias)));
+
+    let mut closest: Option<(usize, &str)> = None;
+    for (canonical, alias) in candidates {
+        let distance = edit_distance(&normalized, &normalize_name(alias));
+        if distance == 0 {
+            return Ok(canonical);
+        }
+        if closest.is_none_or(|(best, _)| distance < best) {
+            closest = Some((distance, canonical));
+        }
+    }
+
+    let valid = TRANSFORM_NAMES.iter().map(|(canonical, _)| *canonical).collect::<Vec<_>>().join(", ");
+    match closest {
+        Some((distance, suggestion)) if distance <= 2 => Err(format!(
+            "unknown transform type `{}`, did you mean `{}`? Valid transform types: {}", name, suggestion, valid
+        )),
+        _ => Err(format!("unknown transform type `{}`. Valid transform types: {}", name, valid)),
+    }
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            current.push((previous[j] + cost).min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+impl Serialize for Transform {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Transform::serialize(self, serializer)
+    }
+}
@@ -0,0 +1,71 @@
+use enrichr::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn run(source: Value, transform: Value) -> Result<Value, EnrichmentError> {
+    common::apply(&Engine::new(), json!({ "items": source }), json!("$.items"), transform)
+}
+
+#[test]
+fn test_indexing() -> Result<(), EnrichmentError> {
+    let items = json!(["a", "b", "c", "d"]);
+
+    assert_eq!(run(items.clone(), json!({ "type": "first" }))?, json!("a"));
+    assert_eq!(run(items.clone(), json!({ "type": "last" }))?, json!("d"));
+    assert_eq!(run(items.clone(), json!({ "type": "at", "index": 1 }))?, json!("b"));
+    assert_eq!(run(items.clone(), json!({ "type": "at", "index": -2 }))?, json!("c"));
+    assert_eq!(run(items.clone(), json!({ "type": "at", "index": 9 }))?, Value::Null);
+    assert_eq!(run(items.clone(), json!({ "type": "slice", "start": 1, "end": -1 }))?, json!(["b", "c"]));
+    assert_eq!(run(items.clone(), json!({ "type": "slice", "start": -10 }))?, items);
+    assert_eq!(run(json!([]), json!({ "type": "first" }))?, Value::Null);
+    assert_eq!(run(items, json!({ "type": "join", "delimiter": "|" }))?, json!("a|b|c|d"));
+    Ok(())
+}
+
+#[test]
+fn test_filter_and_map() -> Result<(), EnrichmentError> {
+    let payments = json!([
+        { "id": "p1", "amount": "12.50", "status": "booked" },
+        { "id": "p2", "amount": "3.00", "status": "rejected" },
+        { "id": "p3", "amount": "7.25", "status": "booked" }
+    ]);
+
+    assert_eq!(
+        run(payments.clone(), json!({ "type": "filter", "condition": { "==": [{ "var": "status" }, "booked"] } }))?,
+        json!([payments[0], payments[2]])
+    );
+    assert_eq!(
+        run(json!(["eur", " usd"]), json!({
+            "type": "map",
+            "transforms": [{ "type": "regexReplace", "pattern": "\\s", "replacement": "" }, { "type": "toUpperCase" }]
+        }))?,
+        json!(["EUR", "USD"])
+    );
+    // A failing element fails the whole map
+    let error = run(payments, json!({ "type": "map", "transforms": [{ "type": "toUpperCase" }] })).unwrap_err();
+    assert!(matches!(error, EnrichmentError::TransformError(_)));
+    Ok(())
+}
+
+#[test]
+fn test_sort_unique_flatten() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!([10, 2, 33, 2]), json!({ "type": "sort" }))?, json!([2, 2, 10, 33]));
+    assert_eq!(run(json!(["b", "c", "a"]), json!({ "type": "sort", "descending": true }))?, json!(["c", "b", "a"]));
+    assert_eq!(
+        run(json!([{ "n": 3 }, { "n": 1 }, { "n": 2 }]), json!({ "type": "sort", "by": "$.n" }))?,
+        json!([{ "n": 1 }, { "n": 2 }, { "n": 3 }])
+    );
+    assert_eq!(run(json!(["a", "b", "a", 1, 1]), json!({ "type": "unique" }))?, json!(["a", "b", 1]));
+    assert_eq!(run(json!([[1, [2]], [3], 4]), json!({ "type": "flatten" }))?, json!([1, [2], 3, 4]));
+    assert_eq!(run(json!([[1, [2]], [3], 4]), json!({ "type": "flatten", "depth": 2 }))?, json!([1, 2, 3, 4]));
+    Ok(())
+}
+
+#[test]
+fn test_count_and_sum() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!([1, 2, 3]), json!({ "type": "count" }))?, json!(3));
+    assert_eq!(run(json!([0.1, "0.2", 3]), json!({ "type": "sum" }))?, json!(3.3));
+    assert!(matches!(run(json!([1, null]), json!({ "type": "sum" })), Err(EnrichmentError::TransformError(_))));
+    Ok(())
+}
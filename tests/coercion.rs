@@ -0,0 +1,87 @@
+use enrichr::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+#[derive(Debug, Default, Serialize, Deserialize, Enrichable)]
+struct Preferences {
+    notifications: bool,
+    newsletter: bool,
+    middle_name: Option<String>,
+    age: u32,
+    account_number: String,
+}
+
+fn data() -> HashMap<String, Value> {
+    serde_json::from_value(json!({
+        "prefs": {
+            "notifications": "Y",
+            "newsletter": "n",
+            "middle_name": "  ",
+            "age": "42",
+            "account_number": 12345678
+        }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_to_boolean_and_null() -> Result<(), EnrichmentError> {
+    let rules: Vec<MappingRule> = serde_json::from_value(json!([
+        { "source": "$.prefs.notifications", "target": "$.notifications", "transform": { "type": "toBoolean" } },
+        {
+            "source": "$.prefs.newsletter",
+            "target": "$.newsletter",
+            "transform": { "type": "toBoolean", "truthy": ["J"], "falsy": ["N"] }
+        },
+        { "source": "$.prefs.middle_name", "target": "$.middle_name", "transform": { "type": "toNull" } }
+    ]))
+    .unwrap();
+
+    let mut prefs = Preferences { middle_name: Some("placeholder".into()), ..Default::default() };
+    prefs.enrich(&data(), &rules)?;
+
+    assert!(prefs.notifications);
+    assert!(!prefs.newsletter);
+    assert_eq!(prefs.middle_name, None);
+    Ok(())
+}
+
+#[test]
+fn test_to_boolean_rejects_unknown_spelling() {
+    let rules: Vec<MappingRule> = serde_json::from_value(json!([
+        {
+            "source": "$.prefs.notifications",
+            "target": "$.notifications",
+            "transform": { "type": "toBoolean", "truthy": ["y"], "caseSensitive": true }
+        }
+    ]))
+    .unwrap();
+
+    let error = Preferences::default().enrich(&data(), &rules).unwrap_err();
+    assert!(matches!(error, EnrichmentError::TransformError(_)));
+}
+
+#[test]
+fn test_setter_coercion() -> Result<(), EnrichmentError> {
+    let rules: Vec<MappingRule> = serde_json::from_value(json!([
+        { "source": "$.prefs.age", "target": "$.age" },
+        { "source": "$.prefs.account_number", "target": "$.account_number" }
+    ]))
+    .unwrap();
+    let spec = Spec::from(rules);
+
+    let error = Preferences::default().enrich_with(&data(), &spec, &Engine::new()).unwrap_err();
+    assert!(matches!(error, EnrichmentError::DataTypeError(_)));
+
+    let mut prefs = Preferences::default();
+    prefs.enrich_with(&data(), &spec, &Engine::new().with_coercion(true))?;
+    assert_eq!(prefs.age, 42);
+    assert_eq!(prefs.account_number, "12345678");
+
+    // Values that cannot be coerced still report the original type error
+    let bad: HashMap<String, Value> = serde_json::from_value(json!({ "prefs": { "age": "forty-two", "account_number": "1" } })).unwrap();
+    let error = Preferences::default().enrich_with(&bad, &spec, &Engine::new().with_coercion(true)).unwrap_err();
+    assert!(matches!(error, EnrichmentError::DataTypeError(_)));
+    Ok(())
+}
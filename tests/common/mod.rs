@@ -0,0 +1,15 @@
+//! Fixture shared by the transform tests
+
+use enrichr::{EnrichmentError, Engine, Spec};
+use serde_json::{json, Value};
+
+/// Apply `transform` to the value `source` selects in `data`, returning the transformed value
+pub fn apply(engine: &Engine, data: Value, source: Value, transform: Value) -> Result<Value, EnrichmentError> {
+    let spec: Spec = serde_json::from_value(json!({
+        "rules": [{ "source": source, "target": "$.value", "transform": transform }]
+    }))
+    .unwrap();
+
+    let output = engine.transform_value(&data, &spec)?;
+    Ok(output.get("value").cloned().unwrap_or(Value::Null))
+}
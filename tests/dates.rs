@@ -0,0 +1,80 @@
+use chrono::{TimeZone, Utc};
+use enrichr::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn run(source: Value, transform: Value) -> Result<Value, EnrichmentError> {
+    let engine = Engine::new().with_clock(FixedClock::new(Utc.with_ymd_and_hms(2024, 1, 5, 12, 0, 0).unwrap()));
+    common::apply(&engine, json!({ "date": source, "settlement": "2024-01-10" }), json!("$.date"), transform)
+}
+
+#[test]
+fn test_parse_mixed_formats() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!("20240101"), json!({ "type": "parseDate", "format": "%Y%m%d" }))?, json!("2024-01-01"));
+    assert_eq!(run(json!("01/02/2024"), json!({ "type": "parseDate", "format": "%m/%d/%Y" }))?, json!("2024-01-02"));
+    assert_eq!(
+        run(json!("2024-01-01T12:00:00Z"), json!({ "type": "parseDate", "format": "rfc3339" }))?,
+        json!("2024-01-01T12:00:00Z")
+    );
+    assert_eq!(
+        run(json!(1704110400000i64), json!({ "type": "parseDate", "format": "epochMillis" }))?,
+        json!("2024-01-01T12:00:00Z")
+    );
+    assert_eq!(
+        run(json!("01.07.2024 09:30"), json!({ "type": "parseDate", "format": "%d.%m.%Y %H:%M", "timezone": "Europe/Berlin" }))?,
+        json!("2024-07-01T09:30:00+02:00")
+    );
+    assert!(run(json!("2024-13-01"), json!({ "type": "parseDate", "format": "%Y-%m-%d" })).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_format_and_convert() -> Result<(), EnrichmentError> {
+    let instant = json!("2024-01-01T23:30:00Z");
+
+    assert_eq!(run(instant.clone(), json!({ "type": "formatDate", "format": "%d/%m/%Y" }))?, json!("01/01/2024"));
+    assert_eq!(
+        run(instant.clone(), json!({ "type": "formatDate", "format": "%Y-%m-%d", "timezone": "Asia/Tokyo" }))?,
+        json!("2024-01-02")
+    );
+    assert_eq!(run(instant.clone(), json!({ "type": "formatDate", "format": "epochSeconds" }))?, json!(1704151800));
+    assert_eq!(
+        run(instant, json!({ "type": "convertTimezone", "timezone": "America/New_York" }))?,
+        json!("2024-01-01T18:30:00-05:00")
+    );
+
+    let error = run(json!("2024-01-01"), json!({ "type": "convertTimezone", "timezone": "Mars/Olympus" })).unwrap_err();
+    assert!(matches!(error, EnrichmentError::SpecError(_)));
+    Ok(())
+}
+
+#[test]
+fn test_date_arithmetic() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!("2024-02-28"), json!({ "type": "addDays", "days": 2 }))?, json!("2024-03-01"));
+    assert_eq!(
+        run(json!("2024-01-05T10:00:00+01:00"), json!({ "type": "addDays", "days": -5 }))?,
+        json!("2023-12-31T10:00:00+01:00")
+    );
+    // Friday plus one business day skips the weekend
+    assert_eq!(run(json!("2024-01-05"), json!({ "type": "addBusinessDays", "days": 1 }))?, json!("2024-01-08"));
+    assert_eq!(
+        run(json!("2024-12-24"), json!({ "type": "addBusinessDays", "days": 1, "holidays": ["2024-12-25", "2024-12-26"] }))?,
+        json!("2024-12-27")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_date_diff() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!("2024-01-01"), json!({ "type": "dateDiff", "to": "$.settlement" }))?, json!(9));
+    assert_eq!(
+        run(json!("2024-01-01"), json!({ "type": "dateDiff", "to": "$.settlement", "unit": "businessDays" }))?,
+        json!(7)
+    );
+    assert_eq!(
+        run(json!("2024-01-05T00:00:00Z"), json!({ "type": "dateDiff", "to": { "now": {} }, "unit": "hours" }))?,
+        json!(12)
+    );
+    Ok(())
+}
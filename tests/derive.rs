@@ -0,0 +1,25 @@
+//! The derive macro must compile without the caller importing anything from enrichr
+
+mod accounts {
+    #[derive(Debug, Default, serde::Serialize, serde::Deserialize, enrichr::Enrichable)]
+    pub struct Account {
+        pub iban: String,
+        pub tags: Vec<String>,
+    }
+}
+
+#[test]
+fn test_derive_uses_absolute_paths() -> Result<(), enrichr::EnrichmentError> {
+    use enrichr::Enrichable;
+
+    let rules: Vec<enrichr::MappingRule> = enrichr::serde_json::from_value(enrichr::serde_json::json!([
+        { "source": "$.iban", "target": "$.iban" },
+        { "source": "$.tag", "target": "$.tags", "mode": "append" }
+    ]))
+    .unwrap();
+    let mut account = accounts::Account::default();
+    account.enrich_value(&enrichr::serde_json::json!({ "iban": "DE89370400440532013000", "tag": "eur" }), &rules)?;
+    assert_eq!(account.iban, "DE89370400440532013000");
+    assert_eq!(account.tags, vec!["eur"]);
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use enrichr::*;
+use serde_json::{json, Value};
+
+fn spec(rules: Value) -> Spec {
+    serde_json::from_value(json!({ "rules": rules })).unwrap()
+}
+
+#[test]
+fn test_nested_targets() -> Result<(), EnrichmentError> {
+    let input = json!({
+        "payment": { "id": "P-1", "amount": "12.50", "currency": "eur" },
+        "debtor": { "name": "ACME", "iban": "DE89370400440532013000" }
+    });
+    let output = transform_value(&input, &spec(json!([
+        { "source": "$.payment.id", "target": "$.header.reference" },
+        { "source": "$.payment.amount", "target": "$.transfer.amount.value", "transform": { "type": "toNumber" } },
+        { "source": "$.payment.currency", "target": "$.transfer.amount.currency", "transform": { "type": "toUpperCase" } },
+        { "source": "$.debtor.name", "target": "$.parties[0].name" },
+        { "source": { "const": "debtor" }, "target": "$.parties[0].role" },
+        { "source": "$.debtor.iban", "target": "$.parties[0].accounts[1]['iban']" }
+    ])))?;
+
+    assert_eq!(
+        output,
+        json!({
+            "header": { "reference": "P-1" },
+            "transfer": { "amount": { "value": 12.5, "currency": "EUR" } },
+            "parties": [{ "name": "ACME", "role": "debtor", "accounts": [null, { "iban": "DE89370400440532013000" }] }]
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_roots_and_multiple_targets() -> Result<(), EnrichmentError> {
+    let input = json!(["a", "b"]);
+    assert_eq!(
+        transform_value(&input, &spec(json!([{ "source": ["$[1]", "$[0]"], "target": ["$[0]", "$[1]"] }])))?,
+        json!(["b", "a"])
+    );
+    assert_eq!(transform_value(&input, &spec(json!([{ "source": "$[0]", "target": "$" }])))?, json!("a"));
+    assert_eq!(transform_value(&input, &spec(json!([])))?, json!({}));
+    Ok(())
+}
+
+#[test]
+fn test_engine_context() -> Result<(), EnrichmentError> {
+    let engine = Engine::new().with_var("partner", json!("bank-a"));
+    let output = engine.transform_value(&json!({}), &spec(json!([
+        { "source": { "context": "partner" }, "target": "$.meta.${partner}.id" }
+    ])))?;
+    assert_eq!(output, json!({ "meta": { "bank-a": { "id": "bank-a" } } }));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_targets() {
+    let input = json!({ "id": "x" });
+    for target in ["$.items[*]", "$..id", "id", "$.a[-1]", "$.a[", "$.a[18446744073709551615]", "$.a[1000000000]", "$.a[1025]"] {
+        let result = transform_value(&input, &spec(json!([{ "source": "$.id", "target": target }])));
+        assert!(matches!(result, Err(EnrichmentError::SpecError(_))), "{}: {:?}", target, result);
+    }
+
+    // Padding up to the limit is allowed
+    let output = transform_value(&input, &spec(json!([{ "source": "$.id", "target": "$.a[1024]" }]))).unwrap();
+    assert_eq!(output["a"].as_array().map(Vec::len), Some(1025));
+
+    // Writing through an existing scalar is a type conflict
+    let result = transform_value(&input, &spec(json!([
+        { "source": "$.id", "target": "$.a" },
+        { "source": "$.id", "target": "$.a.b" }
+    ])));
+    assert!(matches!(result, Err(EnrichmentError::DataTypeError(_))));
+}
@@ -0,0 +1,66 @@
+use enrichr::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn run(source: Value, transform: Value) -> Result<Value, EnrichmentError> {
+    common::apply(&Engine::new(), json!({ "payload": source }), json!("$.payload"), transform)
+}
+
+#[test]
+fn test_base64() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!("Zürich?"), json!({ "type": "base64Encode" }))?, json!("WsO8cmljaD8="));
+    assert_eq!(run(json!("Zürich?"), json!({ "type": "toBase64", "urlSafe": true, "padding": false }))?, json!("WsO8cmljaD8"));
+    assert_eq!(run(json!("WsO8cmljaD8="), json!({ "type": "base64Decode" }))?, json!("Zürich?"));
+    // Decoding accepts input with or without padding
+    assert_eq!(run(json!("WsO8cmljaD8"), json!({ "type": "base64Decode" }))?, json!("Zürich?"));
+
+    let error = run(json!("not base64!"), json!({ "type": "base64Decode" })).unwrap_err();
+    assert!(matches!(error, EnrichmentError::TransformError(_)));
+    // Binary content cannot be represented as a JSON string
+    let error = run(json!("/w=="), json!({ "type": "base64Decode" })).unwrap_err();
+    assert!(matches!(error, EnrichmentError::TransformError(_)));
+    Ok(())
+}
+
+#[test]
+fn test_hex() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!("EUR"), json!({ "type": "hexEncode" }))?, json!("455552"));
+    assert_eq!(run(json!("é"), json!({ "type": "hexEncode", "uppercase": true }))?, json!("C3A9"));
+    assert_eq!(run(json!(42), json!({ "type": "hexEncode" }))?, json!("3432"));
+    assert_eq!(run(json!("c3a9"), json!({ "type": "fromHex" }))?, json!("é"));
+    assert!(run(json!("abc"), json!({ "type": "hexDecode" })).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_url() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!("a b&c=d/é~"), json!({ "type": "urlEncode" }))?, json!("a%20b%26c%3Dd%2F%C3%A9~"));
+    assert_eq!(run(json!("a%20b%26c%3Dd%2F%C3%A9~"), json!({ "type": "urlDecode" }))?, json!("a b&c=d/é~"));
+    assert!(run(json!("%FF"), json!({ "type": "urlDecode" })).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_json() -> Result<(), EnrichmentError> {
+    let payload = json!(r#"{"debtor": {"name": "ACME", "accounts": [1, 2]}}"#);
+    assert_eq!(
+        run(payload.clone(), json!({ "type": "parseJson" }))?,
+        json!({ "debtor": { "name": "ACME", "accounts": [1, 2] } })
+    );
+    assert_eq!(run(payload.clone(), json!({ "type": "parseJson", "path": "$.debtor.name" }))?, json!("ACME"));
+    assert_eq!(run(payload.clone(), json!({ "type": "parseJson", "path": "$.debtor.accounts[1]" }))?, json!(2));
+    assert!(matches!(
+        run(payload, json!({ "type": "parseJson", "path": "$.creditor" })).unwrap_err(),
+        EnrichmentError::JsonPathError(_)
+    ));
+    assert!(matches!(
+        run(json!("{not json"), json!({ "type": "parseJson" })).unwrap_err(),
+        EnrichmentError::TransformError(_)
+    ));
+
+    assert_eq!(run(json!({ "a": [1, "x"] }), json!({ "type": "stringifyJson" }))?, json!(r#"{"a":[1,"x"]}"#));
+    assert_eq!(run(json!([1]), json!({ "type": "toJson", "pretty": true }))?, json!("[\n  1\n]"));
+    assert_eq!(run(json!("quoted"), json!({ "type": "stringifyJson" }))?, json!("\"quoted\""));
+    Ok(())
+}
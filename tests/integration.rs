@@ -1,8 +1,8 @@
-use enrichr::prelude::*;
-use enrichr::enrichable::helpers::default_enrich;
+use enrichr::*;
 use serde_json::json;
+use std::collections::HashMap;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Default, PartialEq)]
 struct User {
     name: String,
     age: u32,
@@ -10,59 +10,52 @@ struct User {
     tags: Vec<String>,
 }
 
-impl Default for User {
-    fn default() -> Self {
-        Self {
-            name: String::new(),
-            age: 0,
-            location: String::new(),
-            tags: Vec::new(),
-        }
-    }
-}
-
 impl Enrichable for User {
-    fn enrich(&mut self, data: &serde_json::Value, spec: &str) -> Result<(), Error> {
-        default_enrich(self, data, spec)
-    }
-    
-    fn set_field(&mut self, field: &str, value: serde_json::Value) -> Result<(), Error> {
+    fn set_field(&mut self, field: &str, value: serde_json::Value) -> Result<(), EnrichmentError> {
         match field {
             "name" => {
                 self.name = value.as_str()
-                    .ok_or_else(|| Error::TypeConversion("Expected string for name".into()))?
+                    .ok_or_else(|| EnrichmentError::DataTypeError("Expected string for name".into()))?
                     .to_string();
             },
             "age" => {
                 self.age = value.as_u64()
-                    .ok_or_else(|| Error::TypeConversion("Expected number for age".into()))?
+                    .ok_or_else(|| EnrichmentError::DataTypeError("Expected number for age".into()))?
                     as u32;
             },
             "location" => {
                 self.location = value.as_str()
-                    .ok_or_else(|| Error::TypeConversion("Expected string for location".into()))?
+                    .ok_or_else(|| EnrichmentError::DataTypeError("Expected string for location".into()))?
                     .to_string();
             },
             "tags" => {
                 self.tags = value.as_array()
-                    .ok_or_else(|| Error::TypeConversion("Expected array for tags".into()))?
+                    .ok_or_else(|| EnrichmentError::DataTypeError("Expected array for tags".into()))?
                     .iter()
                     .map(|v| v.as_str()
-                        .ok_or_else(|| Error::TypeConversion("Expected string array for tags".into()))
+                        .ok_or_else(|| EnrichmentError::DataTypeError("Expected string array for tags".into()))
                         .map(|s| s.to_string()))
                     .collect::<Result<_, _>>()?;
             },
-            _ => return Err(Error::Field(format!("Unknown field: {}", field))),
+            _ => return Err(EnrichmentError::SpecError(format!("Unknown field: {}", field))),
         }
         Ok(())
     }
 }
 
+fn data(value: serde_json::Value) -> HashMap<String, serde_json::Value> {
+    serde_json::from_value(value).unwrap()
+}
+
+fn rules(spec: &str) -> Vec<MappingRule> {
+    serde_json::from_str(spec).unwrap()
+}
+
 #[test]
-fn test_simple_enrichment() -> Result<(), Error> {
+fn test_simple_enrichment() -> Result<(), EnrichmentError> {
     let mut user = User::default();
     
-    let data = json!({
+    let data = data(json!({
         "user": {
             "first_name": "John",
             "last_name": "Doe",
@@ -72,29 +65,29 @@ fn test_simple_enrichment() -> Result<(), Error> {
                 "country": "USA"
             }
         }
-    });
+    }));
     
-    let spec = r#"{
-        "name": {
-            "path": "$.user.first_name",
+    let spec = rules(r#"[
+        {
+            "source": ["$.user.first_name", "$.user.last_name"],
+            "target": "$.name",
             "transform": {
-                "type": "concat",
-                "additional_paths": ["$.user.last_name"],
-                "separator": " "
+                "type": "template",
+                "params": { "template": "{0} {1}" }
             }
         },
-        "age": "$.user.details.age",
-        "location": {
-            "path": "$.user.details.city",
+        { "source": "$.user.details.age", "target": "$.age" },
+        {
+            "source": ["$.user.details.city", "$.user.details.country"],
+            "target": "$.location",
             "transform": {
-                "type": "concat",
-                "additional_paths": ["$.user.details.country"],
-                "separator": ", "
+                "type": "template",
+                "params": { "template": "{0}, {1}" }
             }
         }
-    }"#;
+    ]"#);
     
-    user.enrich(&data, spec)?;
+    user.enrich(&data, &spec)?;
     
     assert_eq!(user.name, "John Doe");
     assert_eq!(user.age, 30);
@@ -104,36 +97,35 @@ fn test_simple_enrichment() -> Result<(), Error> {
 }
 
 #[test]
-fn test_transforms() -> Result<(), Error> {
+fn test_transforms() -> Result<(), EnrichmentError> {
     let mut user = User::default();
     
-    let data = json!({
+    let data = data(json!({
         "profile": {
             "name": "john doe",
             "age": 25,
             "tags_string": "developer,rust,backend"
         }
-    });
+    }));
     
-    let spec = r#"{
-        "name": {
-            "path": "$.profile.name",
-            "transform": {
-                "type": "function",
-                "name": "uppercase"
-            }
+    let spec = rules(r#"[
+        {
+            "source": "$.profile.name",
+            "target": "$.name",
+            "transform": { "type": "toUpperCase" }
         },
-        "age": "$.profile.age",
-        "tags": {
-            "path": "$.profile.tags_string",
+        { "source": "$.profile.age", "target": "$.age" },
+        {
+            "source": "$.profile.tags_string",
+            "target": "$.tags",
             "transform": {
                 "type": "split",
-                "separator": ","
+                "params": { "delimiter": "," }
             }
         }
-    }"#;
+    ]"#);
     
-    user.enrich(&data, spec)?;
+    user.enrich(&data, &spec)?;
     
     assert_eq!(user.name, "JOHN DOE");
     assert_eq!(user.age, 25);
@@ -146,22 +138,22 @@ fn test_transforms() -> Result<(), Error> {
 fn test_missing_path() {
     let mut user = User::default();
     
-    let data = json!({});
-    let spec = r#"{"name": "$.nonexistent.path"}"#;
+    let data = data(json!({}));
+    let spec = rules(r#"[{"source": "$.nonexistent.path", "target": "$.name"}]"#);
     
-    let result = user.enrich(&data, spec);
+    let result = user.enrich(&data, &spec);
     assert!(result.is_err());
-    assert!(matches!(result.unwrap_err(), Error::Path(_)));
+    assert!(matches!(result.unwrap_err(), EnrichmentError::JsonPathError(_)));
 }
 
 #[test]
 fn test_invalid_type() {
     let mut user = User::default();
     
-    let data = json!({"age": "not a number"});
-    let spec = r#"{"age": "$.age"}"#;
+    let data = data(json!({"age": "not a number"}));
+    let spec = rules(r#"[{"source": "$.age", "target": "$.age"}]"#);
     
-    let result = user.enrich(&data, spec);
+    let result = user.enrich(&data, &spec);
     assert!(result.is_err());
-    assert!(matches!(result.unwrap_err(), Error::TypeConversion(_)));
-}
\ No newline at end of file
+    assert!(matches!(result.unwrap_err(), EnrichmentError::DataTypeError(_)));
+}
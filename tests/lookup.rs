@@ -0,0 +1,48 @@
+use enrichr::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn engine() -> Result<Engine, EnrichmentError> {
+    let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+    Ok(Engine::new()
+        .with_lookup_table("reasons", LookupTable::from_file(format!("{}/reason_codes.csv", fixtures))?)
+        .with_lookup_table("countries", LookupTable::from_file(format!("{}/countries.json", fixtures))?))
+}
+
+fn run(source: Value, transform: Value) -> Result<Value, EnrichmentError> {
+    common::apply(&engine()?, json!({ "input": source }), json!("$.input"), transform)
+}
+
+#[test]
+fn test_inline_table() -> Result<(), EnrichmentError> {
+    let lookup = json!({ "type": "lookup", "table": { "1": "pending", "2": "booked", "true": "yes" } });
+
+    assert_eq!(run(json!("2"), lookup.clone())?, json!("booked"));
+    assert_eq!(run(json!(1), lookup.clone())?, json!("pending"));
+    assert_eq!(run(json!(true), lookup.clone())?, json!("yes"));
+
+    let error = run(json!(3), lookup).unwrap_err();
+    assert!(matches!(error, EnrichmentError::TransformError(_)));
+    Ok(())
+}
+
+#[test]
+fn test_missing_values() -> Result<(), EnrichmentError> {
+    let table = json!({ "DE": "Germany" });
+
+    assert_eq!(run(json!("FR"), json!({ "type": "lookup", "table": table, "default": "Unknown" }))?, json!("Unknown"));
+    assert_eq!(run(json!("FR"), json!({ "type": "lookup", "table": table, "onMissing": "passthrough" }))?, json!("FR"));
+    assert_eq!(run(json!("FR"), json!({ "type": "lookup", "table": table, "onMissing": "null" }))?, Value::Null);
+    Ok(())
+}
+
+#[test]
+fn test_named_tables() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!("AM04"), json!({ "type": "lookup", "table": "reasons" }))?, json!("Insufficient funds"));
+    assert_eq!(run(json!("France"), json!({ "type": "lookup", "table": "countries" }))?, json!("FR"));
+
+    let error = run(json!("AM04"), json!({ "type": "lookup", "table": "missing" })).unwrap_err();
+    assert!(matches!(error, EnrichmentError::SpecError(_)));
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use enrichr::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+#[derive(Debug, Default, Serialize, Deserialize, Enrichable)]
+struct Customer {
+    name: String,
+    email: Option<String>,
+    score: u32,
+    tags: Vec<String>,
+    notes: String,
+    attributes: HashMap<String, Value>,
+}
+
+fn enrich(customer: &mut Customer, data: Value, rules: Value) -> Result<(), EnrichmentError> {
+    let rules: Vec<MappingRule> = serde_json::from_value(rules).unwrap();
+    customer.enrich_value(&data, &rules)
+}
+
+#[test]
+fn test_if_empty() -> Result<(), EnrichmentError> {
+    let mut customer = Customer { name: "Jane".into(), ..Default::default() };
+    let rules = json!([
+        { "source": "$.name", "target": "$.name", "mode": "ifEmpty" },
+        { "source": "$.email", "target": "$.email", "mode": "ifEmpty" },
+        { "source": "$.score", "target": "$.score", "mode": "ifEmpty" }
+    ]);
+    enrich(&mut customer, json!({ "name": "Other", "email": "jane@example.com", "score": 7 }), rules.clone())?;
+    assert_eq!(customer.name, "Jane");
+    assert_eq!(customer.email.as_deref(), Some("jane@example.com"));
+    assert_eq!(customer.score, 7);
+
+    // A second source only fills what is still empty
+    enrich(&mut customer, json!({ "name": "Other", "email": "other@example.com", "score": 9 }), rules)?;
+    assert_eq!(customer.email.as_deref(), Some("jane@example.com"));
+    assert_eq!(customer.score, 7);
+    Ok(())
+}
+
+#[test]
+fn test_append() -> Result<(), EnrichmentError> {
+    let mut customer = Customer { tags: vec!["vip".into()], notes: "Called".into(), ..Default::default() };
+    enrich(&mut customer, json!({ "tag": "eu", "more": ["b2b", "new"], "note": "; emailed" }), json!([
+        { "source": "$.tag", "target": "$.tags", "mode": "append" },
+        { "source": "$.more", "target": "$.tags", "mode": "append" },
+        { "source": "$.note", "target": "$.notes", "mode": "append" }
+    ]))?;
+    assert_eq!(customer.tags, vec!["vip", "eu", "b2b", "new"]);
+    assert_eq!(customer.notes, "Called; emailed");
+
+    let error = enrich(&mut customer, json!({ "tag": "x" }), json!([
+        { "source": "$.tag", "target": "$.score", "mode": "append" }
+    ]))
+    .unwrap_err();
+    assert!(matches!(error, EnrichmentError::DataTypeError(_)));
+    Ok(())
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Enrichable)]
+struct Contact {
+    email: Option<String>,
+    phones: Option<Vec<String>>,
+}
+
+#[test]
+fn test_append_to_empty() -> Result<(), EnrichmentError> {
+    let mut contact = Contact::default();
+    let rules: Vec<MappingRule> = serde_json::from_value(json!([
+        { "source": "$.email", "target": "$.email", "mode": "append" },
+        { "source": "$.phone", "target": "$.phones", "mode": "append" }
+    ]))
+    .unwrap();
+    contact.enrich_value(&json!({ "email": "jane@example.com", "phone": "+49 30 1234" }), &rules)?;
+    assert_eq!(contact.email.as_deref(), Some("jane@example.com"));
+    assert_eq!(contact.phones, Some(vec!["+49 30 1234".to_string()]));
+
+    contact.enrich_value(&json!({ "email": ".de", "phone": "+49 30 5678" }), &rules)?;
+    assert_eq!(contact.email.as_deref(), Some("jane@example.com.de"));
+    assert_eq!(contact.phones, Some(vec!["+49 30 1234".to_string(), "+49 30 5678".to_string()]));
+    Ok(())
+}
+
+#[test]
+fn test_merge() -> Result<(), EnrichmentError> {
+    let mut customer = Customer::default();
+    customer.attributes.insert("address".into(), json!({ "city": "Berlin", "zip": "10115" }));
+    enrich(&mut customer, json!({ "crm": { "address": { "zip": "10117", "country": "DE" }, "segment": "retail" } }), json!([
+        { "source": "$.crm", "target": "$.attributes", "mode": "merge" }
+    ]))?;
+    assert_eq!(
+        Value::Object(customer.attributes.clone().into_iter().collect()),
+        json!({ "address": { "city": "Berlin", "zip": "10117", "country": "DE" }, "segment": "retail" })
+    );
+
+    let error = enrich(&mut customer, json!({ "crm": "text" }), json!([
+        { "source": "$.crm", "target": "$.attributes", "mode": "merge" }
+    ]))
+    .unwrap_err();
+    assert!(matches!(error, EnrichmentError::DataTypeError(_)));
+    Ok(())
+}
+
+#[test]
+fn test_document_modes() -> Result<(), EnrichmentError> {
+    let input = json!({ "primary": { "id": "A", "tags": ["x"] }, "fallback": { "id": "B", "extra": { "k": 1 } } });
+    let spec: Spec = serde_json::from_value(json!({ "rules": [
+        { "source": "$.primary.id", "target": "$.id" },
+        { "source": "$.fallback.id", "target": "$.id", "mode": "ifEmpty" },
+        { "source": "$.fallback.id", "target": "$.alt", "mode": "ifEmpty" },
+        { "source": "$.primary.tags", "target": "$.tags", "mode": "append" },
+        { "source": "$.fallback.id", "target": "$.tags", "mode": "append" },
+        { "source": "$.primary", "target": "$.merged", "mode": "merge" },
+        { "source": "$.fallback", "target": "$.merged", "mode": "merge" }
+    ] }))
+    .unwrap();
+
+    assert_eq!(
+        transform_value(&input, &spec)?,
+        json!({
+            "id": "A",
+            "alt": "B",
+            "tags": ["x", "B"],
+            "merged": { "id": "B", "tags": ["x"], "extra": { "k": 1 } }
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_unknown_mode() {
+    let result: Result<MappingRule, _> = serde_json::from_value(json!({ "source": "$.a", "target": "$.a", "mode": "replace" }));
+    assert!(result.is_err());
+}
+
+/// A field type that can only be deserialized
+#[derive(Debug, Default, Deserialize)]
+struct Secret(String);
+
+#[derive(Debug, Default, Deserialize, Enrichable)]
+struct Credentials {
+    user: Option<String>,
+    secret: Option<Secret>,
+}
+
+#[test]
+fn test_mode_on_unserializable_field() {
+    let mut target = Credentials::default();
+    let rules: Vec<MappingRule> = serde_json::from_value(json!([
+        { "source": "$.user", "target": "$.user", "mode": "ifEmpty" },
+        { "source": "$.secret", "target": "$.secret" }
+    ]))
+    .unwrap();
+    target.enrich_value(&json!({ "user": "jane", "secret": "s3cret" }), &rules).unwrap();
+    assert_eq!(target.user.as_deref(), Some("jane"));
+    assert_eq!(target.secret.as_ref().map(|secret| secret.0.as_str()), Some("s3cret"));
+
+    // Fields are read through Serialize, so combining with a field that lacks it fails
+    let rules: Vec<MappingRule> = serde_json::from_value(json!([
+        { "source": "$.secret", "target": "$.secret", "mode": "ifEmpty" }
+    ]))
+    .unwrap();
+    let error = target.enrich_value(&json!({ "secret": "other" }), &rules).unwrap_err();
+    assert!(matches!(&error, EnrichmentError::SpecError(message) if message.contains("does not implement Serialize")), "{:?}", error);
+}
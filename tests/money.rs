@@ -0,0 +1,90 @@
+use enrichr::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn run(data: Value, source: &str, transform: Value) -> Result<Value, EnrichmentError> {
+    common::apply(&Engine::new().with_var("currency", json!("EUR")), data, json!(source), transform)
+}
+
+#[test]
+fn test_parse_and_format() -> Result<(), EnrichmentError> {
+    let data = json!({ "amount": "1.234.567,8", "plain": 1234567.8 });
+
+    assert_eq!(run(data.clone(), "$.amount", json!({ "type": "parseMoney", "decimalSeparator": "," }))?, json!("1234567.8"));
+    assert_eq!(
+        run(data.clone(), "$.plain", json!({ "type": "formatMoney", "currency": "${currency}", "groupSeparator": ",", "includeCode": true }))?,
+        json!("EUR 1,234,567.80")
+    );
+    assert_eq!(run(data, "$.plain", json!({ "type": "formatMoney", "currency": "JPY" }))?, json!("1234568"));
+    Ok(())
+}
+
+#[test]
+fn test_parse_money_grouping() -> Result<(), EnrichmentError> {
+    let parse = |amount: &str, params: Value| {
+        let mut transform = json!({ "type": "parseMoney" });
+        transform.as_object_mut().unwrap().extend(params.as_object().unwrap().clone());
+        run(json!({ "amount": amount }), "$.amount", transform)
+    };
+    assert_eq!(parse("1,234,567.50", json!({}))?, json!("1234567.5"));
+    assert_eq!(parse("-1,234", json!({}))?, json!("-1234"));
+    assert_eq!(parse("999.5", json!({}))?, json!("999.5"));
+    assert_eq!(parse("1'234.5", json!({ "groupSeparator": "'" }))?, json!("1234.5"));
+    assert_eq!(parse("1234,5", json!({ "decimalSeparator": "," }))?, json!("1234.5"));
+
+    // Separators that do not fit the configured locale are rejected instead of guessed
+    for (amount, params) in [
+        ("1.234,56", json!({})),
+        ("1,23.45", json!({})),
+        ("12,3456", json!({})),
+        (",123", json!({})),
+        ("1.2.3", json!({})),
+        ("1.234,56", json!({ "decimalSeparator": ".", "groupSeparator": "." })),
+        ("1,234.56", json!({ "decimalSeparator": "," })),
+    ] {
+        let error = parse(amount, params).unwrap_err();
+        assert!(matches!(error, EnrichmentError::TransformError(_)), "{}: {:?}", amount, error);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_minor_and_major_units() -> Result<(), EnrichmentError> {
+    let data = json!({ "eur": "12.50", "kwd": "1.5", "bhd_minor": 1500, "ccy": "KWD", "fine": "0.125" });
+
+    assert_eq!(run(data.clone(), "$.eur", json!({ "type": "toMinorUnits", "currency": "EUR" }))?, json!(1250));
+    assert_eq!(run(data.clone(), "$.kwd", json!({ "type": "toMinorUnits", "currency": { "source": "$.ccy" } }))?, json!(1500));
+    assert_eq!(run(data.clone(), "$.bhd_minor", json!({ "type": "toMajorUnits", "currency": "BHD" }))?, json!("1.500"));
+    assert_eq!(run(json!({ "minor": 1500.0 }), "$.minor", json!({ "type": "toMajorUnits", "currency": "EUR" }))?, json!("15.00"));
+    assert_eq!(run(json!({ "minor": "1500.00" }), "$.minor", json!({ "type": "toMajorUnits", "currency": "BHD" }))?, json!("1.500"));
+    assert_eq!(run(data.clone(), "$.fine", json!({ "type": "toMinorUnits", "currency": "EUR", "mode": "halfUp" }))?, json!(13));
+
+    let error = run(data, "$.fine", json!({ "type": "toMinorUnits", "currency": "EUR" })).unwrap_err();
+    assert!(error.to_string().contains("more than 2 decimal places"), "{}", error);
+    Ok(())
+}
+
+#[test]
+fn test_sum_is_exact() -> Result<(), EnrichmentError> {
+    let data = json!({ "amounts": [0.1, "0.2", 0.3] });
+
+    assert_eq!(run(data.clone(), "$.amounts", json!({ "type": "sumMoney" }))?, json!("0.6"));
+    assert_eq!(run(data, "$.amounts", json!({ "type": "sumMoney", "currency": "USD" }))?, json!("0.60"));
+    Ok(())
+}
+
+#[test]
+fn test_unknown_currency() {
+    let error = run(json!({ "amount": 1 }), "$.amount", json!({ "type": "toMinorUnits", "currency": "XYZ" })).unwrap_err();
+    assert!(matches!(error, EnrichmentError::ValidationError(_)));
+}
+
+#[cfg(feature = "arbitrary_precision")]
+#[test]
+fn test_large_amounts_keep_every_digit() -> Result<(), EnrichmentError> {
+    let data: Value = serde_json::from_str(r#"{ "amount": 12345678901234567.89 }"#).unwrap();
+
+    assert_eq!(run(data, "$.amount", json!({ "type": "toMinorUnits", "currency": "EUR" }))?, json!(1234567890123456789i64));
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use enrichr::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs::File;
+
+#[derive(Debug, Default, Serialize, Deserialize, Enrichable)]
+struct Transfer {
+    message_type: String,
+    reference: String,
+    value_date: String,
+    currency: String,
+    amount: f64,
+    debtor_account: String,
+    debtor_name: String,
+    creditor_bank: String,
+}
+
+fn mt103() -> Result<std::collections::HashMap<String, Value>, EnrichmentError> {
+    MtParser::new().parse_reader(File::open("tests/fixtures/mt103.txt").expect("fixture exists"))
+}
+
+#[test]
+fn test_mt103() -> Result<(), EnrichmentError> {
+    let data = mt103()?;
+    let rules: Vec<MappingRule> = serde_json::from_value(json!([
+        { "source": "$.block2.messageType", "target": "$.message_type" },
+        { "source": "$.block4.20", "target": "$.reference" },
+        { "source": "$.block4.32A.date", "target": "$.value_date" },
+        { "source": "$.block4.32A.currency", "target": "$.currency" },
+        { "source": "$.block4.32A.amount", "target": "$.amount", "transform": { "type": "toNumber" } },
+        { "source": "$.block4.50K.account", "target": "$.debtor_account" },
+        { "source": "$.block4.50K.nameAndAddress[0]", "target": "$.debtor_name" },
+        { "source": "$.block4.57A.bic", "target": "$.creditor_bank" }
+    ]))
+    .unwrap();
+
+    let mut transfer = Transfer::default();
+    transfer.enrich(&data, &rules)?;
+
+    assert_eq!(transfer.message_type, "103");
+    assert_eq!(transfer.reference, "REF-20240315-01");
+    assert_eq!(transfer.value_date, "2024-03-15");
+    assert_eq!(transfer.currency, "EUR");
+    assert_eq!(transfer.amount, 1234.56);
+    assert_eq!(transfer.debtor_account, "BE68539007547034");
+    assert_eq!(transfer.debtor_name, "ACME TRADING NV");
+    assert_eq!(transfer.creditor_bank, "DEUTDEFF");
+    Ok(())
+}
+
+#[test]
+fn test_blocks() -> Result<(), EnrichmentError> {
+    let data = mt103()?;
+    assert_eq!(
+        data["block1"],
+        json!({
+            "applicationId": "F",
+            "serviceId": "01",
+            "logicalTerminal": "BANKBEBBAXXX",
+            "session": "2222",
+            "sequence": "123456"
+        })
+    );
+    assert_eq!(data["block2"]["receiver"], json!("BANKDEFFXXXX"));
+    assert_eq!(data["block2"]["priority"], json!("N"));
+    assert_eq!(data["block3"], json!({ "108": "MUR-0042", "121": "7de5ba5d-6c2a-4f2c-9b0a-54a1d1b7c0a1" }));
+    assert_eq!(data["block5"], json!({ "CHK": "123456789ABC" }));
+
+    let text = &data["block4"];
+    assert_eq!(text["50K"]["nameAndAddress"], json!(["ACME TRADING NV", "RUE DE LA LOI 1", "1000 BRUSSELS"]));
+    assert_eq!(text["59"], json!({ "account": "DE89370400440532013000", "nameAndAddress": ["JOHN DOE GMBH"] }));
+    // Repeated tags become arrays in message order
+    assert_eq!(text["71F"], json!([{ "currency": "EUR", "amount": "2.50" }, { "currency": "EUR", "amount": "1" }]));
+
+    let output = MtParser::new().parse_str(
+        "{1:F01BANKDEFFAXXX0000000000}{2:O1031200240315BANKBEBBAXXX22221234562403151201N}{4:\n:20:X\n-}",
+    )?;
+    assert_eq!(output["block2"]["direction"], json!("output"));
+    assert_eq!(output["block2"]["sender"], json!("BANKBEBBAXXX"));
+    assert_eq!(output["block2"]["outputDate"], json!("2024-03-15"));
+    Ok(())
+}
+
+#[test]
+fn test_mt940_statement() -> Result<(), EnrichmentError> {
+    let statement = ":20:STMT-1\n:25:DE89370400440532013000\n:28C:1/1\n:60F:C240314EUR1000,00\n\
+        :61:2403150315D250,00NTRFREF-1//BANK-1\nRENT MARCH\n:86:Rent payment\n:62F:C240315EUR750,00\n-";
+    let data = MtParser::new().with_array("61").parse_str(statement)?;
+    let text = &data["block4"];
+
+    assert_eq!(text["60F"], json!({ "mark": "C", "date": "2024-03-14", "currency": "EUR", "amount": "1000.00" }));
+    assert_eq!(
+        text["61"],
+        json!([{
+            "valueDate": "2024-03-15",
+            "entryDate": "2024-03-15",
+            "mark": "D",
+            "fundsCode": null,
+            "amount": "250.00",
+            "transactionType": "NTRF",
+            "reference": "REF-1",
+            "bankReference": "BANK-1",
+            "supplementaryDetails": "RENT MARCH"
+        }])
+    );
+    assert_eq!(text["62F"]["amount"], json!("750.00"));
+
+    // The entry date takes the year closest to the value date
+    let lines = ":61:2501021231D1,00NTRFA\n:61:2412310102C1,00NTRFB\n:61:2403010229C1,00NTRFC\n-";
+    let text = &MtParser::new().parse_str(lines)?["block4"];
+    assert_eq!(text["61"][0]["entryDate"], json!("2024-12-31"));
+    assert_eq!(text["61"][1]["entryDate"], json!("2025-01-02"));
+    assert_eq!(text["61"][2]["entryDate"], json!("2024-02-29"));
+    Ok(())
+}
+
+#[test]
+fn test_malformed_messages() {
+    for message in [
+        "{1:F01BANK}{4:\n:20:X\n-}",
+        "{2:X103}",
+        "{4:\n:20:X\n-",
+        "{4:\n:32A:241315EUR1,00\n-}",
+        "{4:\n:32A:240315EUR1.00\n-}",
+        "{4:\n:61:2403151315D1,00NTRFX\n-}",
+        "no tag here",
+    ] {
+        let result = MtParser::new().parse_str(message);
+        assert!(matches!(result, Err(EnrichmentError::DataTypeError(_))), "{}: {:?}", message, result);
+    }
+}
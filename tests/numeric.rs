@@ -0,0 +1,62 @@
+use enrichr::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn run(source: Value, transform: Value) -> Result<Value, EnrichmentError> {
+    let engine = Engine::new().with_var("fee", json!("0.25"));
+    common::apply(&engine, json!({ "amount": source, "fx": { "rate": 1.1 } }), json!("$.amount"), transform)
+}
+
+#[test]
+fn test_parse_and_scale() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!("12.50"), json!({ "type": "toNumber" }))?, json!(12.5));
+    assert_eq!(run(json!(" 42 "), json!({ "type": "toInteger" }))?, json!(42));
+    assert_eq!(run(json!("12.50"), json!({ "type": "scale", "exponent": 2 }))?, json!(1250));
+    assert_eq!(run(json!(1250), json!({ "type": "scale", "exponent": -2 }))?, json!(12.5));
+    Ok(())
+}
+
+#[test]
+fn test_rounding_modes() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!(2.5), json!({ "type": "round" }))?, json!(2));
+    assert_eq!(run(json!(2.5), json!({ "type": "round", "mode": "halfUp" }))?, json!(3));
+    assert_eq!(run(json!(2.675), json!({ "type": "round", "places": 2, "mode": "half-up" }))?, json!(2.68));
+    assert_eq!(run(json!(-1.25), json!({ "type": "round", "places": 1, "mode": "floor" }))?, json!(-1.3));
+    assert_eq!(run(json!(1.21), json!({ "type": "round", "places": 1, "mode": "ceil" }))?, json!(1.3));
+    assert_eq!(run(json!(7.5), json!({ "type": "toInteger", "mode": "halfEven" }))?, json!(8));
+    assert!(run(json!(7.5), json!({ "type": "toInteger" })).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_arithmetic() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!(0.1), json!({ "type": "add", "operand": 0.2 }))?, json!(0.3));
+    assert_eq!(run(json!(10), json!({ "type": "subtract", "operand": "${fee}" }))?, json!(9.75));
+    assert_eq!(run(json!(100), json!({ "type": "multiply", "operand": { "source": "$.fx.rate" } }))?, json!(110));
+    assert_eq!(run(json!(7), json!({ "type": "divide", "operand": 2 }))?, json!(3.5));
+    assert_eq!(run(json!(-3.5), json!({ "type": "abs" }))?, json!(3.5));
+    Ok(())
+}
+
+#[test]
+fn test_numeric_errors() {
+    let error = run(json!(1), json!({ "type": "divide", "operand": 0 })).unwrap_err();
+    assert!(matches!(error, EnrichmentError::TransformError(_)));
+
+    let error = run(json!(1e27), json!({ "type": "multiply", "operand": 1e10 })).unwrap_err();
+    assert!(error.to_string().contains("overflow"), "{}", error);
+
+    // Integers beyond 64 bits are rejected rather than rounded through f64
+    assert_eq!(run(json!("18446744073709551615"), json!({ "type": "toInteger" })).unwrap(), json!(u64::MAX));
+    let error = run(json!("99999999999999999999"), json!({ "type": "toInteger" })).unwrap_err();
+    assert!(error.to_string().contains("overflow"), "{}", error);
+    let error = run(json!("-9223372036854775809"), json!({ "type": "toNumber" })).unwrap_err();
+    assert!(matches!(error, EnrichmentError::TransformError(_)));
+
+    let error = run(json!("twelve"), json!({ "type": "toNumber" })).unwrap_err();
+    assert!(matches!(error, EnrichmentError::TransformError(_)));
+
+    let error = run(json!(true), json!({ "type": "abs" })).unwrap_err();
+    assert!(matches!(error, EnrichmentError::TransformError(_)));
+}
@@ -0,0 +1,102 @@
+use enrichr::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Address {
+    street: String,
+    city: String,
+    country: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Enrichable)]
+struct Customer {
+    address: Address,
+    attributes: HashMap<String, String>,
+    value: Value,
+}
+
+fn data() -> HashMap<String, Value> {
+    serde_json::from_value(json!({
+        "customer": {
+            "line1": "1 Main St",
+            "town": "Springfield",
+            "profile": { "tier": "gold", "segment": "retail", "internal_score": "88" },
+            "defaults": { "locale": "en", "limits": { "daily": 100, "monthly": 1000 } },
+            "overrides": { "limits": { "daily": 250 } }
+        }
+    }))
+    .unwrap()
+}
+
+fn run(rules: Value) -> Result<Customer, EnrichmentError> {
+    let rules: Vec<MappingRule> = serde_json::from_value(rules).unwrap();
+    let mut customer = Customer::default();
+    customer.enrich(&data(), &rules)?;
+    Ok(customer)
+}
+
+#[test]
+fn test_object_source() -> Result<(), EnrichmentError> {
+    let customer = run(json!([{
+        "source": { "object": {
+            "street": "$.customer.line1",
+            "city": "$.customer.town",
+            "country": { "const": "US" }
+        } },
+        "target": "$.address"
+    }]))?;
+
+    assert_eq!(customer.address, Address {
+        street: "1 Main St".into(),
+        city: "Springfield".into(),
+        country: "US".into(),
+    });
+    Ok(())
+}
+
+#[test]
+fn test_pick_and_omit() -> Result<(), EnrichmentError> {
+    let customer = run(json!([
+        { "source": "$.customer.profile", "target": "$.attributes", "transform": { "type": "omit", "keys": ["internal_score"] } },
+        { "source": "$.customer.profile", "target": "$.value", "transform": { "type": "pick", "keys": ["tier", "missing"] } }
+    ]))?;
+
+    assert_eq!(customer.attributes.len(), 2);
+    assert_eq!(customer.attributes["segment"], "retail");
+    assert_eq!(customer.value, json!({ "tier": "gold" }));
+    Ok(())
+}
+
+#[test]
+fn test_merge() -> Result<(), EnrichmentError> {
+    let sources = json!(["$.customer.defaults", "$.customer.overrides"]);
+
+    let shallow = run(json!([{ "source": sources, "target": "$.value", "transform": { "type": "merge" } }]))?;
+    assert_eq!(shallow.value, json!({ "locale": "en", "limits": { "daily": 250 } }));
+
+    let deep = run(json!([{ "source": sources, "target": "$.value", "transform": { "type": "merge", "deep": true } }]))?;
+    assert_eq!(deep.value, json!({ "locale": "en", "limits": { "daily": 250, "monthly": 1000 } }));
+    Ok(())
+}
+
+#[test]
+fn test_entries_round_trip() -> Result<(), EnrichmentError> {
+    let customer = run(json!([{ "source": "$.customer.defaults.limits", "target": "$.value", "transform": { "type": "entries" } }]))?;
+    assert_eq!(customer.value, json!([{ "key": "daily", "value": 100 }, { "key": "monthly", "value": 1000 }]));
+
+    let rules: Vec<MappingRule> = serde_json::from_value(json!([
+        {
+            "source": { "const": [["tier", "gold"], { "key": "segment", "value": "retail" }] },
+            "target": "$.attributes",
+            "transform": { "type": "fromEntries" }
+        }
+    ]))
+    .unwrap();
+    let mut customer = Customer::default();
+    customer.enrich(&HashMap::new(), &rules)?;
+    assert_eq!(customer.attributes["tier"], "gold");
+    assert_eq!(customer.attributes["segment"], "retail");
+    Ok(())
+}
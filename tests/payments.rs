@@ -0,0 +1,84 @@
+use enrichr::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn run(source: Value, transform: Value) -> Result<Value, EnrichmentError> {
+    common::apply(&Engine::new(), json!({ "identifier": source }), json!("$.identifier"), transform)
+}
+
+fn validation_error(source: &str, transform: Value) -> String {
+    match run(json!(source), transform) {
+        Err(EnrichmentError::ValidationError(message)) => message,
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_iban() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!("de89 3704 0044 0532 0130 00"), json!({ "type": "iban" }))?, json!("DE89370400440532013000"));
+    assert_eq!(
+        run(json!("GB29NWBK60161331926819"), json!({ "type": "iban", "format": "print" }))?,
+        json!("GB29 NWBK 6016 1331 9268 19")
+    );
+    assert_eq!(run(json!("NO93-8601-1117-947"), json!({ "type": "validateIban" }))?, json!("NO9386011117947"));
+
+    assert!(validation_error("DE88370400440532013000", json!({ "type": "iban" })).contains("position 3"));
+    assert!(validation_error("DE8937040044053201300", json!({ "type": "iban" })).contains("missing from position 22"));
+    assert!(validation_error("DE893704004405320130000", json!({ "type": "iban" })).contains("unexpected from position 23"));
+    assert!(validation_error("ZZ89370400440532013000", json!({ "type": "iban" })).contains("'ZZ' at position 1"));
+    assert!(validation_error("DEX9370400440532013000", json!({ "type": "iban" })).contains("position 3"));
+    // Positions count in the text as written, or in the quoted compacted form after grouping is removed
+    assert!(validation_error("DE89 3704.0044", json!({ "type": "iban" })).contains("'.' at position 10"));
+    assert!(validation_error("DE89 3704 0044 0532 0130 00!", json!({ "type": "iban" })).contains("'!' at position 28"));
+    let error = validation_error("de89 3704 0044 0532 0130 0", json!({ "type": "iban" }));
+    assert!(error.contains("'DE8937040044053201300': DE IBANs have 22 characters, missing from position 22"), "{}", error);
+    assert!(matches!(run(json!(42), json!({ "type": "iban" })), Err(EnrichmentError::TransformError(_))));
+    Ok(())
+}
+
+#[test]
+fn test_iban_components() -> Result<(), EnrichmentError> {
+    assert_eq!(
+        run(json!("GB29 NWBK 6016 1331 9268 19"), json!({ "type": "ibanComponents" }))?,
+        json!({
+            "countryCode": "GB",
+            "checkDigits": "29",
+            "bban": "NWBK60161331926819",
+            "bankCode": "NWBK",
+            "branchCode": "601613"
+        })
+    );
+    assert_eq!(run(json!("DE89370400440532013000"), json!({ "type": "parseIban", "component": "bankCode" }))?, json!("37040044"));
+    assert_eq!(run(json!("DE89370400440532013000"), json!({ "type": "ibanComponents", "component": "branchCode" }))?, Value::Null);
+    // The Italian BBAN starts with a check character before the bank code
+    assert_eq!(
+        run(json!("IT60X0542811101000000123456"), json!({ "type": "ibanComponents", "component": "bankCode" }))?,
+        json!("05428")
+    );
+    assert!(validation_error("DE88370400440532013000", json!({ "type": "ibanComponents" })).contains("mod-97"));
+    Ok(())
+}
+
+#[test]
+fn test_bic() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!("deut de ff"), json!({ "type": "bic" }))?, json!("DEUTDEFF"));
+    assert_eq!(run(json!("DEUTDEFF"), json!({ "type": "bic", "expand": true }))?, json!("DEUTDEFFXXX"));
+    assert_eq!(run(json!("NWBKGB2L123"), json!({ "type": "swift", "expand": true }))?, json!("NWBKGB2L123"));
+
+    assert!(validation_error("DEUTDEF", json!({ "type": "bic" })).contains("found 7"));
+    assert!(validation_error("DE1TDEFF", json!({ "type": "bic" })).contains("position 3"));
+    assert!(validation_error("DEUTZZFF", json!({ "type": "bic" })).contains("'ZZ' at position 5"));
+    Ok(())
+}
+
+#[test]
+fn test_country_and_currency_codes() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!(" de "), json!({ "type": "countryCode" }))?, json!("DE"));
+    assert!(validation_error("UK", json!({ "type": "countryCode" })).contains("position 1"));
+    assert!(validation_error("DEU", json!({ "type": "iso3166" })).contains("position 1"));
+
+    assert_eq!(run(json!("chf"), json!({ "type": "currencyCode" }))?, json!("CHF"));
+    assert!(validation_error("EUX", json!({ "type": "currencyCode" })).contains("ISO 4217"));
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use enrichr::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn engine() -> Engine {
+    Engine::new()
+        .with_hash_key("analytics", "Jefe")
+        .with_token_vault(InMemoryTokenVault::new())
+}
+
+fn run_with(engine: &Engine, source: Value, transform: Value) -> Result<Value, EnrichmentError> {
+    common::apply(engine, json!({ "pii": source }), json!("$.pii"), transform)
+}
+
+fn run(source: Value, transform: Value) -> Result<Value, EnrichmentError> {
+    run_with(&engine(), source, transform)
+}
+
+#[test]
+fn test_mask() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!("DE89370400440532013000"), json!({ "type": "mask" }))?, json!("******************3000"));
+    assert_eq!(run(json!(12345678), json!({ "type": "mask", "keepFirst": 2, "keepLast": 2, "character": "#" }))?, json!("12####78"));
+    assert_eq!(run(json!("123"), json!({ "type": "mask" }))?, json!("***"));
+    assert_eq!(run(json!("123"), json!({ "type": "mask", "keepFirst": usize::MAX, "keepLast": 1 }))?, json!("***"));
+    assert_eq!(run(json!("a@b.io"), json!({ "type": "mask", "preset": "email", "keepFirst": usize::MAX, "keepLast": usize::MAX }))?, json!("*@b.io"));
+    assert_eq!(run(json!("4111 1111 1111 1111"), json!({ "type": "mask", "preset": "pan" }))?, json!("4111 11** **** 1111"));
+    // Presets cap visibility at the PCI DSS limit
+    assert_eq!(
+        run(json!("4111-1111-1111-1111"), json!({ "type": "mask", "preset": "pan", "keepFirst": 8 }))?,
+        json!("4111-11**-****-1111")
+    );
+    assert_eq!(run(json!("jane.roe@example.com"), json!({ "type": "mask", "preset": "email" }))?, json!("j*******@example.com"));
+    assert_eq!(run(Value::Null, json!({ "type": "mask" }))?, Value::Null);
+
+    let error = run(json!("not a card"), json!({ "type": "mask", "preset": "pan" })).unwrap_err();
+    assert!(matches!(error, EnrichmentError::TransformError(_)));
+    Ok(())
+}
+
+#[test]
+fn test_hash() -> Result<(), EnrichmentError> {
+    assert_eq!(
+        run(json!("abc"), json!({ "type": "hash" }))?,
+        json!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+    );
+    // RFC 4231 test case 2
+    assert_eq!(
+        run(json!("what do ya want for nothing?"), json!({ "type": "hash", "key": "analytics" }))?,
+        json!("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+    );
+
+    let error = run(json!("abc"), json!({ "type": "hash", "key": "missing" })).unwrap_err();
+    assert!(matches!(error, EnrichmentError::SpecError(_)));
+    Ok(())
+}
+
+#[test]
+fn test_redact() -> Result<(), EnrichmentError> {
+    assert_eq!(run(json!("secret"), json!({ "type": "redact" }))?, json!("[REDACTED]"));
+    assert_eq!(
+        run(json!("Contact jane@example.com or joe@example.org"), json!({
+            "type": "redact",
+            "pattern": "[\\w.+-]+@[\\w-]+\\.[\\w.]+",
+            "replacement": "<email>"
+        }))?,
+        json!("Contact <email> or <email>")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_tokenize() -> Result<(), EnrichmentError> {
+    let vault = InMemoryTokenVault::new();
+    let first = vault.tokenize("iban", "DE89370400440532013000")?;
+    assert_eq!(vault.tokenize("iban", "DE89370400440532013000")?, first);
+    assert_ne!(vault.tokenize("iban", "GB29NWBK60161331926819")?, first);
+    assert_eq!(vault.detokenize("iban", &first)?.as_deref(), Some("DE89370400440532013000"));
+    assert_eq!(vault.detokenize("card", &first)?, None);
+
+    let engine = engine();
+    let token = run_with(&engine, json!("4111111111111111"), json!({ "type": "tokenize", "namespace": "pan" }))?;
+    assert_eq!(run_with(&engine, json!("4111111111111111"), json!({ "type": "tokenize", "namespace": "pan" }))?, token);
+
+    let error = run_with(&Engine::new(), json!("4111111111111111"), json!({ "type": "tokenize" })).unwrap_err();
+    assert!(matches!(error, EnrichmentError::SpecError(_)));
+    Ok(())
+}
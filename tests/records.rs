@@ -0,0 +1,131 @@
+use enrichr::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs::File;
+use std::io::{BufReader, Cursor};
+
+#[derive(Debug, Default, Serialize, Deserialize, Enrichable)]
+struct Settlement {
+    reference: String,
+    value_date: String,
+    amount: String,
+    currency: String,
+    counterparty: Option<String>,
+}
+
+fn csv_rules() -> Vec<MappingRule> {
+    serde_json::from_value(json!([
+        { "source": "$.reference", "target": "$.reference" },
+        { "source": "$.value_date", "target": "$.value_date" },
+        {
+            "source": "$.amount",
+            "target": "$.amount",
+            "transform": { "type": "parseMoney", "decimalSeparator": "," }
+        },
+        { "source": "$.currency", "target": "$.currency" },
+        { "source": "$.counterparty", "target": "$.counterparty", "transform": { "type": "toNull" } }
+    ]))
+    .unwrap()
+}
+
+#[test]
+fn test_csv_rows() -> Result<(), EnrichmentError> {
+    let rules = csv_rules();
+    let file = File::open("tests/fixtures/settlements.csv").expect("fixture exists");
+
+    let mut settlements = Vec::new();
+    for row in CsvParser::new().with_delimiter(b';').records(file)? {
+        let mut settlement = Settlement::default();
+        settlement.enrich(&row?, &rules)?;
+        settlements.push(settlement);
+    }
+
+    assert_eq!(settlements.len(), 2);
+    assert_eq!(settlements[0].reference, "SET-001");
+    assert_eq!(settlements[0].amount, "1250");
+    assert_eq!(settlements[0].counterparty.as_deref(), Some("ACME Trading"));
+    assert_eq!(settlements[1].currency, "CHF");
+    assert_eq!(settlements[1].amount, "99.9");
+    assert_eq!(settlements[1].counterparty, None);
+    Ok(())
+}
+
+#[test]
+fn test_csv_errors() -> Result<(), EnrichmentError> {
+    let mut rows = CsvParser::new().records(Cursor::new("a,b\n1,2\n3\n"))?;
+    assert_eq!(rows.next().unwrap()?["b"], json!("2"));
+    assert!(matches!(rows.next(), Some(Err(EnrichmentError::DataTypeError(_)))));
+
+    assert!(matches!(
+        CsvParser::new().records(Cursor::new("a,a\n1,2\n")),
+        Err(EnrichmentError::DataTypeError(_))
+    ));
+    // Whitespace is kept when trimming is disabled
+    let mut rows = CsvParser::new().with_trim(false).records(Cursor::new("a\n x \n"))?;
+    assert_eq!(rows.next().unwrap()?["a"], json!(" x "));
+    Ok(())
+}
+
+#[test]
+fn test_fixed_width_lines() -> Result<(), EnrichmentError> {
+    let layout: FixedWidthLayout = serde_json::from_value(json!({
+        "columns": [
+            { "name": "reference", "start": 1, "length": 10 },
+            { "name": "value_date", "start": 11, "length": 8 },
+            { "name": "amount", "start": 19, "length": 12 },
+            { "name": "currency", "start": 31, "length": 3 },
+            { "name": "counterparty", "start": 34, "length": 20 }
+        ]
+    }))
+    .unwrap();
+    let rules: Vec<MappingRule> = serde_json::from_value(json!([
+        { "source": "$.reference", "target": "$.reference" },
+        {
+            "source": "$.value_date",
+            "target": "$.value_date",
+            "transform": { "type": "parseDate", "format": "%Y%m%d" }
+        },
+        {
+            "source": "$.amount",
+            "target": "$.amount",
+            "transform": { "type": "toMajorUnits", "currency": { "source": "$.currency" } }
+        },
+        { "source": "$.currency", "target": "$.currency" },
+        { "source": "$.counterparty", "target": "$.counterparty", "transform": { "type": "toNull" } }
+    ]))
+    .unwrap();
+
+    let file = BufReader::new(File::open("tests/fixtures/settlements.txt").expect("fixture exists"));
+    let settlements = layout.records(file)?
+        .map(|row| {
+            let mut settlement = Settlement::default();
+            settlement.enrich(&row?, &rules)?;
+            Ok(settlement)
+        })
+        .collect::<Result<Vec<_>, EnrichmentError>>()?;
+
+    // The blank trailing line is skipped
+    assert_eq!(settlements.len(), 2);
+    assert_eq!(settlements[0].value_date, "2024-03-15");
+    assert_eq!(settlements[0].amount, "1250.00");
+    assert_eq!(settlements[0].counterparty.as_deref(), Some("ACME TRADING"));
+    // The second line ends before the counterparty column
+    assert_eq!(settlements[1].amount, "99.90");
+    assert_eq!(settlements[1].counterparty, None);
+    Ok(())
+}
+
+#[test]
+fn test_fixed_width_layout() {
+    let layout = FixedWidthLayout::new().with_column("code", 1, 3).with_column("rest", 4, 5);
+    assert_eq!(layout.parse_line("AB"), [("code".to_string(), json!("AB")), ("rest".to_string(), json!(""))].into());
+
+    let invalid = FixedWidthLayout::new().with_column("code", 0, 3);
+    assert!(matches!(invalid.records(Cursor::new("")), Err(EnrichmentError::SpecError(_))));
+    let duplicate = FixedWidthLayout::new().with_column("code", 1, 3).with_column("code", 4, 5);
+    assert!(matches!(duplicate.records(Cursor::new("")), Err(EnrichmentError::SpecError(_))));
+
+    // A column running to the end of the line
+    let open_ended = FixedWidthLayout::new().with_column("code", 1, 3).with_column("rest", 4, usize::MAX);
+    assert_eq!(open_ended.parse_line("ABCDEF")["rest"], json!("DEF"));
+}
@@ -0,0 +1,61 @@
+use enrichr::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn run(source: &str, transform: Value) -> Result<Value, EnrichmentError> {
+    common::apply(&Engine::new(), json!({ "text": source }), json!("$.text"), transform)
+}
+
+#[test]
+fn test_match_and_extract() -> Result<(), EnrichmentError> {
+    let reference = "INV-2024-00042";
+
+    assert_eq!(run(reference, json!({ "type": "regexMatch", "pattern": "^INV-\\d{4}-" }))?, json!(true));
+    assert_eq!(run(reference, json!({ "type": "regexMatch", "pattern": "^CN-" }))?, json!(false));
+    assert_eq!(run(reference, json!({ "type": "regexExtract", "pattern": "\\d+$" }))?, json!("00042"));
+    assert_eq!(run(reference, json!({ "type": "regexExtract", "pattern": "-(\\d{4})-", "group": 1 }))?, json!("2024"));
+    assert_eq!(
+        run(reference, json!({ "type": "regexExtract", "pattern": "(?P<number>\\d+)$", "group": "number" }))?,
+        json!("00042")
+    );
+    assert_eq!(run(reference, json!({ "type": "regexExtract", "pattern": "^CN-(\\d+)", "group": 1 }))?, Value::Null);
+
+    let error = run(reference, json!({ "type": "regexExtract", "pattern": "\\d+", "group": "year" })).unwrap_err();
+    assert!(matches!(error, EnrichmentError::TransformError(_)));
+    Ok(())
+}
+
+#[test]
+fn test_captures_and_replace() -> Result<(), EnrichmentError> {
+    assert_eq!(
+        run("GB29NWBK60161331926819", json!({
+            "type": "regexCaptures",
+            "pattern": "^(?P<country>[A-Z]{2})(?P<check>\\d{2})(?P<bank>[A-Z]{4})?"
+        }))?,
+        json!({ "country": "GB", "check": "29", "bank": "NWBK" })
+    );
+    assert_eq!(
+        run("2024-01-31", json!({
+            "type": "regexReplace",
+            "pattern": "(?P<y>\\d{4})-(?P<m>\\d{2})-(?P<d>\\d{2})",
+            "replacement": "${d}.${m}.${y}"
+        }))?,
+        json!("31.01.2024")
+    );
+    assert_eq!(run("a  b   c", json!({ "type": "regexReplace", "pattern": "\\s+", "replacement": " " }))?, json!("a b c"));
+    assert_eq!(
+        run("a  b   c", json!({ "type": "regexReplace", "pattern": "\\s+", "replacement": "_", "first": true }))?,
+        json!("a_b   c")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_invalid_pattern_rejected_at_load() {
+    let error = serde_json::from_value::<Vec<MappingRule>>(json!([
+        { "source": "$.text", "target": "$.value", "transform": { "type": "regexMatch", "pattern": "(unclosed" } }
+    ]))
+    .unwrap_err();
+    assert!(error.to_string().contains("Invalid regex pattern"), "{}", error);
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "schemars")]
+
+use enrichr::spec_schema;
+use std::fs;
+use std::path::Path;
+
+const SCHEMA_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/schema/spec.schema.json");
+
+/// Fails when the checked-in schema is stale; rerun with `ENRICHR_UPDATE_SCHEMA=1` to regenerate it
+#[test]
+fn test_checked_in_schema_is_current() {
+    let generated = serde_json::to_string_pretty(&spec_schema()).unwrap() + "\n";
+
+    if std::env::var_os("ENRICHR_UPDATE_SCHEMA").is_some() {
+        fs::create_dir_all(Path::new(SCHEMA_PATH).parent().unwrap()).unwrap();
+        fs::write(SCHEMA_PATH, &generated).unwrap();
+    }
+
+    let checked_in = fs::read_to_string(SCHEMA_PATH).unwrap();
+    assert_eq!(checked_in, generated, "schema/spec.schema.json is out of date");
+}
+
+#[test]
+fn test_schema_requires_transform_params() {
+    let schema = spec_schema();
+    let split = schema["definitions"]["Transform"]["oneOf"].as_array().unwrap().iter()
+        .find(|variant| variant["anyOf"][0]["properties"]["type"]["examples"][0] == "split")
+        .unwrap();
+
+    let inline = &split["anyOf"][0];
+    assert_eq!(inline["required"], serde_json::json!(["type", "delimiter"]));
+    assert_eq!(inline["additionalProperties"], false);
+
+    let nested = &split["anyOf"][1];
+    assert_eq!(nested["properties"]["params"]["required"], serde_json::json!(["delimiter"]));
+}
+
+#[test]
+fn test_schema_accepts_transform_aliases() {
+    let schema = spec_schema();
+    let upper = schema["definitions"]["Transform"]["oneOf"].as_array().unwrap().iter()
+        .find(|variant| variant["anyOf"][0]["properties"]["type"]["examples"][0] == "toUpperCase")
+        .unwrap();
+    let tag = &upper["anyOf"][0]["properties"]["type"];
+    assert_eq!(tag["examples"], serde_json::json!(["toUpperCase", "upper", "uppercase"]));
+    assert_eq!(upper["anyOf"][1]["properties"]["type"], *tag);
+
+    let pattern = regex::Regex::new(tag["pattern"].as_str().unwrap()).unwrap();
+    for name in ["toUpperCase", "ToUpperCase", "to_upper_case", "to-upper-case", "TO_UPPER_CASE", "upper", "UPPERCASE"] {
+        assert!(pattern.is_match(name), "{} should match", name);
+    }
+    for name in ["toLowerCase", "upperCased", "to upper case", "xupper"] {
+        assert!(!pattern.is_match(name), "{} should not match", name);
+    }
+}
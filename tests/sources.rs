@@ -0,0 +1,107 @@
+use chrono::{TimeZone, Utc};
+use enrichr::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+
+#[derive(Debug, Default, Serialize, Deserialize, Enrichable)]
+struct Record {
+    id: String,
+    created_at: String,
+    created_ms: i64,
+    batch_no: u64,
+    channel: String,
+    country: String,
+}
+
+fn engine() -> Engine {
+    Engine::new()
+        .with_clock(FixedClock::new(Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap()))
+        .with_uuid_generator(SequentialUuidGenerator::new(1))
+        .with_var("channel", json!("api"))
+}
+
+fn spec() -> Spec {
+    serde_json::from_value(json!({
+        "rules": [
+            { "source": { "uuid": {} }, "target": "$.id" },
+            { "source": { "now": { "format": "%Y-%m-%d %H:%M" } }, "target": "$.created_at" },
+            { "source": { "now": { "format": "epochMillis" } }, "target": "$.created_ms" },
+            { "source": { "sequence": "batch" }, "target": "$.batch_no" },
+            { "source": { "context": "channel" }, "target": "$.channel" },
+            { "source": { "const": "DE" }, "target": "$.country" }
+        ]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_computed_sources() -> Result<(), EnrichmentError> {
+    let engine = engine();
+    let spec = spec();
+    let data = HashMap::new();
+
+    let mut first = Record::default();
+    first.enrich_with(&data, &spec, &engine)?;
+
+    assert_eq!(first.id, "00000000-0000-0000-0000-000000000001");
+    assert_eq!(first.created_at, "2024-01-02 03:04");
+    assert_eq!(first.created_ms, 1_704_164_645_000);
+    assert_eq!(first.batch_no, 1);
+    assert_eq!(first.channel, "api");
+    assert_eq!(first.country, "DE");
+
+    let mut second = Record::default();
+    second.enrich_with(&data, &spec, &engine)?;
+
+    assert_eq!(second.id, "00000000-0000-0000-0000-000000000002");
+    assert_eq!(second.batch_no, 2);
+    Ok(())
+}
+
+#[test]
+fn test_missing_context_value() {
+    let spec: Spec = serde_json::from_value(json!({
+        "rules": [{ "source": { "context": "tenant" }, "target": "$.channel" }]
+    }))
+    .unwrap();
+
+    let result = Record::default().enrich_with(&HashMap::new(), &spec, &engine());
+    assert!(matches!(result.unwrap_err(), EnrichmentError::SpecError(_)));
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Enrichable)]
+struct Greeting {
+    name: String,
+    title: Option<String>,
+}
+
+#[test]
+fn test_coalesce() -> Result<(), EnrichmentError> {
+    let spec: Spec = serde_json::from_value(json!({
+        "rules": [
+            { "source": { "coalesce": ["$.user.nickname", "$.user.first_name", "$.user.login"] }, "target": "$.name" },
+            { "source": { "coalesce": ["$.user.title"] }, "target": "$.title" }
+        ]
+    }))
+    .unwrap();
+
+    let data: HashMap<String, serde_json::Value> = serde_json::from_value(json!({
+        "user": { "nickname": null, "first_name": "Jane", "login": "jroe" }
+    }))
+    .unwrap();
+    let mut greeting = Greeting { title: Some("Dr".into()), ..Default::default() };
+    greeting.enrich_with(&data, &spec, &engine())?;
+    assert_eq!(greeting.name, "Jane");
+    assert_eq!(greeting.title, None);
+
+    let spec: Spec = serde_json::from_value(json!({
+        "vars": { "fallback": "guest" },
+        "rules": [{ "source": { "coalesce": ["$.user.nickname", "$.user.login"], "default": "${fallback}" }, "target": "$.name" }]
+    }))
+    .unwrap();
+    let mut greeting = Greeting::default();
+    greeting.enrich_with(&HashMap::new(), &spec, &engine())?;
+    assert_eq!(greeting.name, "guest");
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use enrichr::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn run(source: &str, transform: Value) -> Result<Value, EnrichmentError> {
+    common::apply(&Engine::new(), json!({ "text": source }), json!("$.text"), transform)
+}
+
+#[test]
+fn test_trim_pad_truncate() -> Result<(), EnrichmentError> {
+    assert_eq!(run("  ref 1  ", json!({ "type": "trim" }))?, json!("ref 1"));
+    assert_eq!(run("  ref 1  ", json!({ "type": "trimStart" }))?, json!("ref 1  "));
+    assert_eq!(run("  ref 1  ", json!({ "type": "trimEnd" }))?, json!("  ref 1"));
+    assert_eq!(run("000120", json!({ "type": "trimStart", "characters": "0" }))?, json!("120"));
+
+    assert_eq!(run("42", json!({ "type": "padLeft", "length": 6, "fill": "0" }))?, json!("000042"));
+    assert_eq!(run("Zoë", json!({ "type": "padRight", "length": 5, "fill": "." }))?, json!("Zoë.."));
+    assert_eq!(run("already long", json!({ "type": "padLeft", "length": 3 }))?, json!("already long"));
+    assert_eq!(run("42", json!({ "type": "padLeft", "length": 1024 }))?.as_str().map(str::len), Some(1024));
+    // Oversized lengths and empty fills are rejected when the spec is loaded
+    for params in [json!({ "type": "padLeft", "length": 1025 }), json!({ "type": "padRight", "length": 4, "fill": "" })] {
+        assert!(serde_json::from_value::<Transform>(params.clone()).is_err(), "{} should not parse", params);
+    }
+
+    assert_eq!(run("Payment for invoice 42", json!({ "type": "truncate", "length": 10 }))?, json!("Payment..."));
+    assert_eq!(run("Payment", json!({ "type": "truncate", "length": 10 }))?, json!("Payment"));
+    // The family emoji is one grapheme made of several code points
+    assert_eq!(
+        run("👨‍👩‍👧 family", json!({ "type": "truncate", "length": 2, "ellipsis": "…" }))?,
+        json!("👨‍👩‍👧…")
+    );
+    // A limit shorter than the ellipsis cuts the ellipsis too
+    assert_eq!(run("Payment", json!({ "type": "truncate", "length": 2 }))?, json!(".."));
+    assert_eq!(run("Payment", json!({ "type": "truncate", "length": 0 }))?, json!(""));
+    Ok(())
+}
+
+#[test]
+fn test_unicode_normalization() -> Result<(), EnrichmentError> {
+    let decomposed = "Cafe\u{301}";
+
+    assert_eq!(run(decomposed, json!({ "type": "normalize" }))?, json!("Café"));
+    assert_eq!(run("Café", json!({ "type": "normalize", "form": "NFD" }))?, json!(decomposed));
+    assert_eq!(run("ﬁle №1", json!({ "type": "normalize", "form": "nfkc" }))?, json!("file No1"));
+    assert_eq!(run("Crème Brûlée, Łódź", json!({ "type": "stripDiacritics" }))?, json!("Creme Brulee, Łodz"));
+    assert_eq!(run(" Jane \t\n  Roe ", json!({ "type": "collapseWhitespace" }))?, json!("Jane Roe"));
+    Ok(())
+}
+
+#[test]
+fn test_case_styles() -> Result<(), EnrichmentError> {
+    assert_eq!(run("jean-luc o'NEIL", json!({ "type": "titleCase" }))?, json!("Jean-Luc O'Neil"));
+    assert_eq!(run("debtor account_id", json!({ "type": "camelCase" }))?, json!("debtorAccountId"));
+    assert_eq!(run("parseHTTPResponse", json!({ "type": "snake_case" }))?, json!("parse_http_response"));
+    assert_eq!(run("Debtor Account ID", json!({ "type": "kebab-case" }))?, json!("debtor-account-id"));
+    assert_eq!(run("ÉcoleNormale", json!({ "type": "kebabCase" }))?, json!("école-normale"));
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use enrichr::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn render(source: Value, transform: Value) -> Result<Value, EnrichmentError> {
+    let data = json!({
+        "person": { "first": "Jane", "middle": null, "last": "Roe", "vip": true },
+        "payment": { "amount": 12.5, "currency": "EUR", "id": 42, "tags": [] }
+    });
+    common::apply(&Engine::new().with_var("bank", json!("ACME")), data, source, transform)
+}
+
+#[test]
+fn test_named_placeholders_and_defaults() -> Result<(), EnrichmentError> {
+    assert_eq!(
+        render(json!("$.person"), json!({ "type": "template", "template": "{first} {middle?}{last}" }))?,
+        json!("Jane Roe")
+    );
+    assert_eq!(
+        render(json!("$.person"), json!({ "type": "template", "template": "{first} {middle?-} {last}" }))?,
+        json!("Jane - Roe")
+    );
+    assert_eq!(
+        render(json!({ "const": null }), json!({
+            "type": "template",
+            "template": "{name} paid {amount} {currency}",
+            "bindings": {
+                "name": "$.person.first",
+                "amount": "$.payment.amount",
+                "currency": "$.payment.currency"
+            }
+        }))?,
+        json!("Jane paid 12.5 EUR")
+    );
+
+    let error = render(json!("$.person"), json!({ "type": "template", "template": "{first} {middle}" })).unwrap_err();
+    assert!(matches!(error, EnrichmentError::TransformError(_)));
+    Ok(())
+}
+
+#[test]
+fn test_format_specifiers() -> Result<(), EnrichmentError> {
+    let payment = json!("$.payment");
+
+    assert_eq!(render(payment.clone(), json!({ "type": "template", "template": "{amount:.2}" }))?, json!("12.50"));
+    assert_eq!(render(payment.clone(), json!({ "type": "template", "template": "{id:08}" }))?, json!("00000042"));
+    assert_eq!(render(payment.clone(), json!({ "type": "template", "template": "[{id:>5}]" }))?, json!("[   42]"));
+    assert_eq!(render(payment.clone(), json!({ "type": "template", "template": "[{currency:*<6}]" }))?, json!("[EUR***]"));
+    assert_eq!(render(payment.clone(), json!({ "type": "template", "template": "[{currency:^7}]" }))?, json!("[  EUR  ]"));
+    assert_eq!(render(payment, json!({ "type": "template", "template": "{amount:010.3}" }))?, json!("000012.500"));
+    Ok(())
+}
+
+#[test]
+fn test_sections_escapes_and_variables() -> Result<(), EnrichmentError> {
+    assert_eq!(
+        render(json!("$.person"), json!({ "type": "template", "template": "{#vip}VIP {/vip}{first}{^middle} (no middle name){/middle}" }))?,
+        json!("VIP Jane (no middle name)")
+    );
+    assert_eq!(
+        render(json!("$.payment"), json!({ "type": "template", "template": "{{id}} = {id}{#tags} tagged{/tags}" }))?,
+        json!("{id} = 42")
+    );
+    assert_eq!(
+        render(json!({ "const": ["DE", 7] }), json!({ "type": "template", "template": "${bank}/{0}/{1}" }))?,
+        json!("ACME/DE/7")
+    );
+    // `$${` escapes the variable syntax, leaving a literal `$`
+    assert_eq!(
+        render(json!({ "const": [42] }), json!({ "type": "template", "template": "Total: $${0}, ${bank}, $${{bank}}, $5" }))?,
+        json!("Total: $42, ACME, ${bank}, $5")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_invalid_templates_rejected_at_load() {
+    for template in ["{first", "{#vip}VIP", "{#vip}{/first}", "a } b", "{amount:x}", "{}", "{amount:1025}", "{amount:99999999999999}", "{amount:>99999999999999999999999}"] {
+        let result = serde_json::from_value::<Transform>(json!({ "type": "template", "template": template }));
+        assert!(result.is_err(), "{} should not parse", template);
+    }
+    assert!(Template::parse("{amount:1024}").is_ok());
+    assert!(matches!(Template::parse("{amount:1025}"), Err(EnrichmentError::SpecError(_))));
+}
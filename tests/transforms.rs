@@ -0,0 +1,110 @@
+use enrichr::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+
+#[derive(Debug, Default, Serialize, Deserialize, Enrichable)]
+struct Account {
+    holder: String,
+    reference: String,
+    code: String,
+    number: String,
+}
+
+fn data() -> HashMap<String, serde_json::Value> {
+    serde_json::from_value(json!({
+        "account": {
+            "first_name": "Jane",
+            "last_name": "Roe",
+            "reference": "REF-2024-0001",
+            "bank_code": "DE_BANK",
+            "number": 12345678
+        }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_inline_and_nested_params() -> Result<(), EnrichmentError> {
+    let rules: Vec<MappingRule> = serde_json::from_value(json!([
+        {
+            "source": ["$.account.first_name", "$.account.last_name"],
+            "target": "$.holder",
+            "transform": { "type": "concat", "delimiter": " " }
+        },
+        {
+            "source": "$.account.reference",
+            "target": "$.reference",
+            "transform": { "type": "substring", "params": { "start": 4 } }
+        },
+        {
+            "source": "$.account.bank_code",
+            "target": "$.code",
+            "transform": { "type": "replace", "from": "_", "to": "-" }
+        },
+        {
+            "source": "$.account.number",
+            "target": "$.number",
+            "transform": { "type": "toString" }
+        }
+    ]))
+    .unwrap();
+
+    let mut account = Account::default();
+    account.enrich(&data(), &rules)?;
+
+    assert_eq!(account.holder, "Jane Roe");
+    assert_eq!(account.reference, "2024-0001");
+    assert_eq!(account.code, "DE-BANK");
+    assert_eq!(account.number, "12345678");
+    Ok(())
+}
+
+#[test]
+fn test_unknown_param_is_rejected() {
+    let result = serde_json::from_value::<Transform>(json!({
+        "type": "split",
+        "params": { "delimeter": "," }
+    }));
+    assert!(result.unwrap_err().to_string().contains("unknown field `delimeter`"));
+
+    let result = serde_json::from_value::<Transform>(json!({ "type": "toUpperCase", "locale": "tr" }));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_transform_serializes_inline() {
+    let transform: Transform = serde_json::from_value(json!({
+        "type": "split",
+        "params": { "delimiter": "," }
+    }))
+    .unwrap();
+
+    assert_eq!(serde_json::to_value(&transform).unwrap(), json!({ "type": "split", "delimiter": "," }));
+}
+
+#[test]
+fn test_transform_name_spellings() {
+    for name in ["toUpperCase", "ToUpperCase", "to_upper_case", "TO_UPPER_CASE", "to-upper-case", "uppercase", "upper"] {
+        let transform: Transform = serde_json::from_value(json!({ "type": name })).unwrap();
+        assert!(matches!(transform, Transform::ToUpperCase {}), "{} was not accepted", name);
+    }
+    // Only `_` and `-` are ignored, matching the spec schema
+    for name in ["to upper case", "to.upper.case", "toUpperCase!"] {
+        let error = serde_json::from_value::<Transform>(json!({ "type": name })).unwrap_err().to_string();
+        assert!(error.contains("did you mean `toUpperCase`?"), "{}", error);
+    }
+
+    let transform: Transform = serde_json::from_value(json!({ "type": "Split", "params": { "delimiter": "," } })).unwrap();
+    assert!(matches!(transform, Transform::Split(_)));
+}
+
+#[test]
+fn test_unknown_transform_name_suggests_closest() {
+    let error = serde_json::from_value::<Transform>(json!({ "type": "splt" })).unwrap_err().to_string();
+    assert!(error.contains("did you mean `split`?"), "{}", error);
+    assert!(error.contains("Valid transform types: toString, toUpperCase"), "{}", error);
+
+    let error = serde_json::from_value::<Transform>(json!({ "type": "frobnicate" })).unwrap_err().to_string();
+    assert!(!error.contains("did you mean"), "{}", error);
+}
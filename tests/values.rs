@@ -0,0 +1,104 @@
+use enrichr::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default, Serialize, Deserialize, Enrichable)]
+struct Summary {
+    first_id: String,
+    count: u64,
+    label: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Order {
+    id: String,
+    customer: Customer,
+    lines: Vec<Line>,
+}
+
+#[derive(Debug, Serialize)]
+struct Customer {
+    name: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Line {
+    sku: String,
+    quantity: u32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Enrichable)]
+struct Invoice {
+    order_id: String,
+    customer_name: String,
+    first_sku: String,
+    line_count: u64,
+}
+
+fn rules(value: serde_json::Value) -> Vec<MappingRule> {
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn test_array_root() -> Result<(), EnrichmentError> {
+    let data = json!([{ "id": "a" }, { "id": "b" }]);
+    let mut summary = Summary::default();
+    summary.enrich_value(&data, &rules(json!([
+        { "source": "$[0].id", "target": "$.first_id" },
+        { "source": "$", "target": "$.count", "transform": { "type": "count" } }
+    ])))?;
+
+    assert_eq!(summary.first_id, "a");
+    assert_eq!(summary.count, 2);
+    Ok(())
+}
+
+#[test]
+fn test_scalar_root() -> Result<(), EnrichmentError> {
+    let mut summary = Summary::default();
+    summary.enrich_value(&json!("pending"), &rules(json!([
+        { "source": "$", "target": "$.label", "transform": { "type": "toUpperCase" } }
+    ])))?;
+    assert_eq!(summary.label, "PENDING");
+    Ok(())
+}
+
+#[test]
+fn test_typed_source() -> Result<(), EnrichmentError> {
+    let order = Order {
+        id: "ORD-7".into(),
+        customer: Customer { name: "ACME".into() },
+        lines: vec![Line { sku: "SKU-1".into(), quantity: 2 }, Line { sku: "SKU-2".into(), quantity: 1 }],
+    };
+    let mapping = rules(json!([
+        { "source": "$.id", "target": "$.order_id" },
+        { "source": "$.customer.name", "target": "$.customer_name" },
+        { "source": "$.lines[0].sku", "target": "$.first_sku" },
+        { "source": "$.lines", "target": "$.line_count", "transform": { "type": "count" } }
+    ]));
+
+    let mut invoice = Invoice::default();
+    invoice.enrich_from(&order, &mapping)?;
+    assert_eq!(invoice.order_id, "ORD-7");
+    assert_eq!(invoice.customer_name, "ACME");
+    assert_eq!(invoice.first_sku, "SKU-1");
+    assert_eq!(invoice.line_count, 2);
+
+    let engine = Engine::new().with_var("prefix", json!("INV"));
+    let spec = Spec::from(rules(json!([
+        { "source": "$.id", "target": "$.order_id", "transform": { "type": "template", "template": "${prefix}-{0}" } }
+    ])));
+    let mut invoice = Invoice::default();
+    invoice.enrich_from_with(&order, &spec, &engine)?;
+    assert_eq!(invoice.order_id, "INV-ORD-7");
+    Ok(())
+}
+
+#[test]
+fn test_unserializable_source() {
+    let source: BTreeMap<(u8, u8), u8> = [((1, 2), 3)].into();
+    let mut summary = Summary::default();
+    let error = summary.enrich_from(&source, &[]).unwrap_err();
+    assert!(matches!(error, EnrichmentError::SerializationError(_)));
+}
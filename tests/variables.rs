@@ -0,0 +1,90 @@
+use enrichr::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+
+#[derive(Debug, Default, Serialize, Deserialize, Enrichable)]
+struct Payment {
+    account: String,
+    currency: String,
+    reference: String,
+    tags: Vec<String>,
+    priority: u32,
+}
+
+fn data() -> HashMap<String, serde_json::Value> {
+    serde_json::from_value(json!({
+        "accounts": {
+            "acme": { "number": "12345678" },
+            "globex": { "number": "87654321" }
+        },
+        "invoice": { "id": "INV-1", "labels": "urgent|export" }
+    }))
+    .unwrap()
+}
+
+fn spec() -> Spec {
+    serde_json::from_value(json!({
+        "vars": {
+            "bank": "acme",
+            "default_currency": "EUR",
+            "separator": "|",
+            "priority": 3
+        },
+        "rules": [
+            { "source": "$.accounts.${bank}.number", "target": "$.account" },
+            { "source": { "const": "${default_currency}" }, "target": "$.currency" },
+            {
+                "source": ["$.invoice.id"],
+                "target": "$.reference",
+                "transform": { "type": "template", "params": { "template": "${bank}/{0}" } }
+            },
+            {
+                "source": "$.invoice.labels",
+                "target": "$.tags",
+                "transform": { "type": "split", "params": { "delimiter": "${separator}" } }
+            },
+            { "source": { "const": "${priority}" }, "target": "$.priority" }
+        ]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_spec_vars() -> Result<(), EnrichmentError> {
+    let mut payment = Payment::default();
+    payment.enrich_with(&data(), &spec(), &Engine::new())?;
+
+    assert_eq!(payment.account, "12345678");
+    assert_eq!(payment.currency, "EUR");
+    assert_eq!(payment.reference, "acme/INV-1");
+    assert_eq!(payment.tags, vec!["urgent", "export"]);
+    assert_eq!(payment.priority, 3);
+    Ok(())
+}
+
+#[test]
+fn test_runtime_context_overrides_spec_vars() -> Result<(), EnrichmentError> {
+    let engine = Engine::new()
+        .with_var("bank", json!("globex"))
+        .with_var("default_currency", json!("GBP"));
+
+    let mut payment = Payment::default();
+    payment.enrich_with(&data(), &spec(), &engine)?;
+
+    assert_eq!(payment.account, "87654321");
+    assert_eq!(payment.currency, "GBP");
+    assert_eq!(payment.reference, "globex/INV-1");
+    Ok(())
+}
+
+#[test]
+fn test_unknown_variable() {
+    let spec: Spec = serde_json::from_value(json!({
+        "rules": [{ "source": "$.accounts.${missing}.number", "target": "$.account" }]
+    }))
+    .unwrap();
+
+    let result = Payment::default().enrich_with(&data(), &spec, &Engine::new());
+    assert!(matches!(result.unwrap_err(), EnrichmentError::SpecError(_)));
+}
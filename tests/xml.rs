@@ -0,0 +1,93 @@
+#![cfg(feature = "xml")]
+
+use enrichr::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs::File;
+use std::io::BufReader;
+
+#[derive(Debug, Default, Serialize, Deserialize, Enrichable)]
+struct Payment {
+    message_id: String,
+    initiator: String,
+    debtor: String,
+    debtor_iban: String,
+    transactions: i64,
+    end_to_end_id: String,
+    amount: String,
+    currency: String,
+}
+
+fn pain001() -> Result<std::collections::HashMap<String, Value>, EnrichmentError> {
+    let file = File::open("tests/fixtures/pain.001.xml").expect("fixture exists");
+    XmlParser::new().parse_reader(BufReader::new(file))
+}
+
+#[test]
+fn test_iso20022_message() -> Result<(), EnrichmentError> {
+    let data = pain001()?;
+    let rules: Vec<MappingRule> = serde_json::from_value(json!([
+        { "source": "$.Document.CstmrCdtTrfInitn.GrpHdr.MsgId", "target": "$.message_id" },
+        { "source": "$.Document.CstmrCdtTrfInitn.GrpHdr.InitgPty.Nm", "target": "$.initiator" },
+        { "source": "$.Document.CstmrCdtTrfInitn.PmtInf.Dbtr.Nm", "target": "$.debtor" },
+        { "source": "$.Document.CstmrCdtTrfInitn.PmtInf.DbtrAcct.Id.IBAN", "target": "$.debtor_iban" },
+        {
+            "source": "$.Document.CstmrCdtTrfInitn.PmtInf.CdtTrfTxInf",
+            "target": "$.transactions",
+            "transform": { "type": "count" }
+        },
+        { "source": "$.Document.CstmrCdtTrfInitn.PmtInf.CdtTrfTxInf[0].PmtId.EndToEndId", "target": "$.end_to_end_id" },
+        { "source": "$.Document.CstmrCdtTrfInitn.PmtInf.CdtTrfTxInf[1].Amt.InstdAmt['#text']", "target": "$.amount" },
+        { "source": "$.Document.CstmrCdtTrfInitn.PmtInf.CdtTrfTxInf[1].Amt.InstdAmt['@Ccy']", "target": "$.currency" }
+    ]))
+    .unwrap();
+
+    let mut payment = Payment::default();
+    payment.enrich(&data, &rules)?;
+
+    assert_eq!(payment.message_id, "MSG-2024-0001");
+    assert_eq!(payment.initiator, "ACME & Sons");
+    assert_eq!(payment.debtor, "ACME <Treasury>");
+    assert_eq!(payment.debtor_iban, "DE89370400440532013000");
+    assert_eq!(payment.transactions, 2);
+    assert_eq!(payment.end_to_end_id, "E2E-1");
+    assert_eq!(payment.amount, "20");
+    assert_eq!(payment.currency, "USD");
+    Ok(())
+}
+
+#[test]
+fn test_conventions() -> Result<(), EnrichmentError> {
+    let data = pain001()?;
+    let document = &data["Document"];
+    assert_eq!(document["@xmlns"], json!("urn:iso:std:iso:20022:tech:xsd:pain.001.001.09"));
+    assert_eq!(document["@xmlns:xsi"], json!("http://www.w3.org/2001/XMLSchema-instance"));
+    // Values stay text, and empty elements become empty strings
+    assert_eq!(document["CstmrCdtTrfInitn"]["GrpHdr"]["NbOfTxs"], json!("2"));
+    assert_eq!(document["CstmrCdtTrfInitn"]["PmtInf"]["CdtTrfTxInf"][1]["RmtInf"]["Ustrd"], json!(""));
+    assert_eq!(document["CstmrCdtTrfInitn"]["PmtInf"]["CdtTrfTxInf"][0]["Amt"]["InstdAmt"], json!({ "@Ccy": "EUR", "#text": "100.50" }));
+
+    // Prefixes are dropped from element names
+    let data = XmlParser::new().parse_str(r#"<doc:Document xmlns:doc="urn:x"><doc:Id>1</doc:Id>tail</doc:Document>"#)?;
+    assert_eq!(data["Document"], json!({ "@xmlns:doc": "urn:x", "Id": "1", "#text": "tail" }));
+    Ok(())
+}
+
+#[test]
+fn test_forced_arrays() -> Result<(), EnrichmentError> {
+    let xml = "<PmtInf><CdtTrfTxInf><Id>1</Id></CdtTrfTxInf></PmtInf>";
+    assert_eq!(XmlParser::new().parse_str(xml)?["PmtInf"], json!({ "CdtTrfTxInf": { "Id": "1" } }));
+    assert_eq!(
+        XmlParser::new().with_array("CdtTrfTxInf").parse_str(xml)?["PmtInf"],
+        json!({ "CdtTrfTxInf": [{ "Id": "1" }] })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_malformed_xml() {
+    for xml in ["<a><b></a>", "<a>", "", "<a/><b/>", "text"] {
+        let result = XmlParser::new().parse_str(xml);
+        assert!(matches!(result, Err(EnrichmentError::DataTypeError(_))), "{}: {:?}", xml, result);
+    }
+}